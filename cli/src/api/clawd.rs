@@ -1,8 +1,17 @@
+use crate::category::Category;
 use crate::error::ClawdError;
+use crate::search::paginate;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{ETAG, IF_NONE_MATCH};
 use reqwest::StatusCode;
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct DownloadResponse {
     pub repo: String,
     pub path: String,
@@ -10,7 +19,7 @@ pub struct DownloadResponse {
     pub git_ref: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct ListResponse {
     pub skills: Vec<SkillResponse>,
@@ -20,7 +29,7 @@ pub struct ListResponse {
     pub total_pages: u32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 #[allow(dead_code)]
 pub struct SkillResponse {
     pub id: String,
@@ -32,10 +41,101 @@ pub struct SkillResponse {
     pub author: Option<Author>,
     pub download_count: i64,
     pub rating: f64,
+    #[serde(default, alias = "isVerified")]
+    pub is_verified: bool,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// Name of the configured registry this came from, set by clawd
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
 }
 
+/// Full registry entry for a single skill, as returned by the detail endpoint.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct SkillDetailResponse {
+    #[serde(flatten)]
+    pub skill: SkillResponse,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub contributors: Vec<String>,
+    pub created_at: Option<String>,
+    pub requirements: Option<Requirements>,
+    /// README.mdx body, when the registry includes it
+    pub readme: Option<String>,
+}
+
+/// Format of the registry index document this client understands.
+pub const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Snapshot of every skill in a registry, published as a single document.
 #[derive(Deserialize)]
+pub struct IndexDocument {
+    pub version: u32,
+    pub skills: Vec<IndexEntry>,
+}
+
+/// A skill in the registry index, carrying its full README frontmatter.
+#[derive(Deserialize, Serialize, Clone)]
 #[allow(dead_code)]
+pub struct IndexEntry {
+    #[serde(flatten)]
+    pub skill: SkillResponse,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub contributors: Vec<String>,
+    pub created_at: Option<String>,
+    pub requirements: Option<Requirements>,
+    pub repo: Option<String>,
+    pub path: Option<String>,
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+impl From<SkillResponse> for IndexEntry {
+    fn from(skill: SkillResponse) -> Self {
+        Self {
+            skill,
+            version: None,
+            license: None,
+            contributors: Vec::new(),
+            created_at: None,
+            requirements: None,
+            repo: None,
+            path: None,
+            git_ref: None,
+        }
+    }
+}
+
+pub enum IndexFetch {
+    /// The copy matching the supplied ETag is still current.
+    NotModified,
+    Updated {
+        document: IndexDocument,
+        etag: Option<String>,
+    },
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct Requirements {
+    pub bash_version: Option<String>,
+    /// Other skills, as `[registry:]author/name[@range]`
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Executables that must be on PATH, as `name[<range>]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub python_packages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub node_packages: Vec<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
 pub struct Author {
     pub name: String,
     pub github: String,
@@ -43,25 +143,192 @@ pub struct Author {
     pub avatar: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortOrder {
+    Downloads,
+    Rating,
+    Updated,
+    Name,
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Downloads => "downloads",
+            Self::Rating => "rating",
+            Self::Updated => "updated",
+            Self::Name => "name",
+        }
+    }
+}
+
+/// Filters and ordering shared by `list` and `search`. Sent to the registry as
+/// query parameters and applied locally when searching the cached index.
+#[derive(Debug, Clone, Default)]
+pub struct SkillFilters {
+    pub category: Option<Category>,
+    pub tags: Vec<String>,
+    pub author: Option<String>,
+    pub verified_only: bool,
+    pub sort: Option<SortOrder>,
+}
+
+impl SkillFilters {
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(category) = self.category {
+            pairs.push(("category", category.as_str().to_string()));
+        }
+        for tag in &self.tags {
+            pairs.push(("tag", tag.clone()));
+        }
+        if let Some(author) = &self.author {
+            pairs.push(("author", author.clone()));
+        }
+        if self.verified_only {
+            pairs.push(("verified", "true".to_string()));
+        }
+        if let Some(sort) = self.sort {
+            pairs.push(("sort", sort.as_str().to_string()));
+        }
+        pairs
+    }
+
+    pub fn matches(&self, skill: &SkillResponse) -> bool {
+        if let Some(category) = self.category {
+            if Category::from_str(&skill.category) != Some(category) {
+                return false;
+            }
+        }
+
+        if !self
+            .tags
+            .iter()
+            .all(|tag| skill.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        {
+            return false;
+        }
+
+        if let Some(author) = &self.author {
+            if skill.id.split('/').next() != Some(author.as_str()) {
+                return false;
+            }
+        }
+
+        !self.verified_only || skill.is_verified
+    }
+
+    /// Whether no filter or sort order is set.
+    pub fn is_empty(&self) -> bool {
+        self.query_pairs().is_empty()
+    }
+
+    /// Reorders skills by the requested sort key. Leaves the order untouched
+    /// when no sort was requested so relevance ranking is preserved.
+    pub fn sort(&self, skills: &mut [SkillResponse]) {
+        match self.sort {
+            Some(SortOrder::Downloads) => skills.sort_by_key(|s| Reverse(s.download_count)),
+            Some(SortOrder::Rating) => skills.sort_by(|a, b| b.rating.total_cmp(&a.rating)),
+            Some(SortOrder::Updated) => skills.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
+            Some(SortOrder::Name) => skills.sort_by(|a, b| a.id.cmp(&b.id)),
+            None => {}
+        }
+    }
+}
+
+/// Largest page size the registry accepts.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Skills per list page in a static registry, the default `--limit`.
+pub const STATIC_PAGE_SIZE: u32 = 20;
+
+/// Where a static registry keeps each document, relative to its root. The
+/// paths mirror the API's so one directory tree can be served either way.
+pub mod layout {
+    pub const INDEX: &str = "api/index.json";
+
+    pub fn page(page: u32) -> String {
+        format!("api/skills/page-{}.json", page)
+    }
+
+    pub fn skill(author: &str, name: &str) -> String {
+        format!("api/skills/{}/{}.json", author, name)
+    }
+
+    pub fn download(author: &str, name: &str) -> String {
+        format!("api/skills/{}/{}/download.json", author, name)
+    }
+}
+
+/// How a registry is read, chosen by the scheme of its URL.
+enum Backend {
+    /// A clawd API server
+    Api,
+    /// Files in `layout`, served by any static host (`static+https://...`)
+    Static,
+    /// Files in `layout` in a local directory (`file:///...`)
+    Directory(PathBuf),
+}
+
 pub struct ClawdClient {
+    /// The URL as configured, scheme prefix included
     base_url: String,
+    /// The HTTP root requests go to
+    root: String,
+    backend: Backend,
     client: reqwest::Client,
 }
 
 impl ClawdClient {
     pub fn new(base_url: String) -> Self {
+        let (backend, root) = if base_url.starts_with("file:") {
+            (Backend::Directory(file_url_path(&base_url)), String::new())
+        } else if let Some(url) = base_url.strip_prefix("static+") {
+            (Backend::Static, url.to_string())
+        } else {
+            (Backend::Api, base_url.clone())
+        };
+
         Self {
             base_url,
+            root,
+            backend,
             client: reqwest::Client::new(),
         }
     }
 
+    /// Sends `token` as a bearer token with every request.
+    pub fn with_token(mut self, token: Option<&str>) -> Self {
+        if let Some(client) = token.and_then(authorized_client) {
+            self.client = client;
+        }
+        self
+    }
+
+    /// Whether the registry is a local directory, which is always current
+    /// and never worth caching.
+    pub fn is_local(&self) -> bool {
+        matches!(self.backend, Backend::Directory(_))
+    }
+
+    /// Whether the registry is a set of static files rather than an API.
+    pub fn is_static(&self) -> bool {
+        !matches!(self.backend, Backend::Api)
+    }
+
     pub async fn get_download_info(
         &self,
         author: &str,
         name: &str,
     ) -> Result<DownloadResponse, ClawdError> {
-        let url = format!("{}/api/skills/{}/{}/download", self.base_url, author, name);
+        if self.is_static() {
+            return self
+                .read_static(&layout::download(author, name))
+                .await?
+                .ok_or_else(|| ClawdError::SkillNotFound(format!("{}/{}", author, name)));
+        }
+
+        let url = format!("{}/api/skills/{}/{}/download", self.root, author, name);
 
         let response = self.client.get(&url).send().await?;
 
@@ -73,15 +340,291 @@ impl ClawdClient {
         }
     }
 
-    pub async fn list_skills(&self, page: u32, limit: u32) -> Result<ListResponse, ClawdError> {
-        let url = format!("{}/api/skills?page={}&limit={}", self.base_url, page, limit);
+    pub async fn search_skills(
+        &self,
+        query: &str,
+        page: u32,
+        limit: u32,
+        filters: &SkillFilters,
+    ) -> Result<ListResponse, ClawdError> {
+        if self.is_static() {
+            return Err(ClawdError::SearchUnsupported(self.base_url.clone()));
+        }
+
+        let url = format!("{}/api/skills/search", self.root);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("q", query.to_string()),
+                ("page", page.to_string()),
+                ("limit", limit.to_string()),
+            ])
+            .query(&filters.query_pairs())
+            .send()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(response.json().await?),
+            StatusCode::NOT_FOUND | StatusCode::NOT_IMPLEMENTED => {
+                Err(ClawdError::SearchUnsupported(self.base_url.clone()))
+            }
+            StatusCode::TOO_MANY_REQUESTS => Err(ClawdError::RateLimitExceeded),
+            status => Err(ClawdError::InvalidResponse(format!("HTTP {}", status))),
+        }
+    }
+
+    pub async fn get_skill(
+        &self,
+        author: &str,
+        name: &str,
+    ) -> Result<SkillDetailResponse, ClawdError> {
+        if self.is_static() {
+            return self
+                .read_static(&layout::skill(author, name))
+                .await?
+                .ok_or_else(|| ClawdError::SkillNotFound(format!("{}/{}", author, name)));
+        }
+
+        let url = format!("{}/api/skills/{}/{}", self.root, author, name);
 
         let response = self.client.get(&url).send().await?;
 
+        match response.status() {
+            StatusCode::OK => Ok(response.json().await?),
+            StatusCode::NOT_FOUND => Err(ClawdError::SkillNotFound(format!("{}/{}", author, name))),
+            StatusCode::TOO_MANY_REQUESTS => Err(ClawdError::RateLimitExceeded),
+            status => Err(ClawdError::InvalidResponse(format!("HTTP {}", status))),
+        }
+    }
+
+    /// Lazily walks every page of the listing. The next page is only
+    /// requested once the previous one has been consumed.
+    pub fn list_pages<'a>(
+        &'a self,
+        limit: u32,
+        filters: &'a SkillFilters,
+    ) -> impl Stream<Item = Result<ListResponse, ClawdError>> + 'a {
+        stream::try_unfold(Some(1), move |page| async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let response = self.list_skills(page, limit, filters).await?;
+            let next = (page < response.total_pages).then_some(page + 1);
+            Ok(Some((response, next)))
+        })
+    }
+
+    /// Streams every skill in the registry, page by page.
+    pub fn list_all<'a>(
+        &'a self,
+        filters: &'a SkillFilters,
+    ) -> impl Stream<Item = Result<SkillResponse, ClawdError>> + 'a {
+        self.list_pages(MAX_PAGE_SIZE, filters)
+            .map_ok(|page| stream::iter(page.skills.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Fetches the registry index document, revalidating with `etag` when a
+    /// cached copy exists.
+    pub async fn get_index(&self, etag: Option<&str>) -> Result<IndexFetch, ClawdError> {
+        if self.is_local() {
+            let document: IndexDocument = self
+                .read_static(layout::INDEX)
+                .await?
+                .ok_or_else(|| ClawdError::IndexUnsupported(self.base_url.clone()))?;
+            check_index_version(&document)?;
+            return Ok(IndexFetch::Updated {
+                document,
+                etag: None,
+            });
+        }
+
+        // A static host serves the index at the same path as the API, and
+        // usually honors ETags too.
+        let url = format!("{}/{}", self.root, layout::INDEX);
+
+        let mut request = self.client.get(&url);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
+                let document: IndexDocument = response.json().await?;
+                check_index_version(&document)?;
+                Ok(IndexFetch::Updated { document, etag })
+            }
+            StatusCode::NOT_MODIFIED if etag.is_some() => Ok(IndexFetch::NotModified),
+            StatusCode::NOT_FOUND => Err(ClawdError::IndexUnsupported(self.base_url.clone())),
+            StatusCode::TOO_MANY_REQUESTS => Err(ClawdError::RateLimitExceeded),
+            status => Err(ClawdError::InvalidResponse(format!("HTTP {}", status))),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn list_skills(
+        &self,
+        page: u32,
+        limit: u32,
+        filters: &SkillFilters,
+    ) -> Result<ListResponse, ClawdError> {
+        // Static pages hold the whole catalog in a fixed order, so filtering
+        // or sorting means reading all of them before cutting out the page.
+        if self.is_static() {
+            if filters.is_empty() {
+                return self.list_static(page, limit).await;
+            }
+            let mut skills = self.read_static_pages().await?;
+            skills.retain(|s| filters.matches(s));
+            filters.sort(&mut skills);
+            return Ok(paginate(skills, page, limit));
+        }
+
+        let url = format!("{}/api/skills?page={}&limit={}", self.root, page, limit);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&filters.query_pairs())
+            .send()
+            .await?;
+
         match response.status() {
             StatusCode::OK => Ok(response.json().await?),
             StatusCode::TOO_MANY_REQUESTS => Err(ClawdError::RateLimitExceeded),
             status => Err(ClawdError::InvalidResponse(format!("HTTP {}", status))),
         }
     }
+
+    /// Serves `page` of `limit` skills out of a static registry's fixed-size
+    /// pages, reading only the pages that overlap it.
+    async fn list_static(&self, page: u32, limit: u32) -> Result<ListResponse, ClawdError> {
+        let first = self.read_static_page(1).await?;
+        let size = first.limit.max(1) as u64;
+        let limit = limit.max(1);
+        let total = first.total as u64;
+        let start = (page.max(1) as u64 - 1) * limit as u64;
+        let end = (start + limit as u64).min(total);
+
+        let mut skills = Vec::new();
+        let mut first = Some(first);
+        let mut number = start / size + 1;
+        while start < end && (number - 1) * size < end {
+            let static_page = match first.take().filter(|_| number == 1) {
+                Some(page) => page,
+                None => self.read_static_page(number as u32).await?,
+            };
+            let offset = (number - 1) * size;
+            skills.extend(
+                static_page
+                    .skills
+                    .into_iter()
+                    .zip(offset..)
+                    .filter(|(_, i)| (start..end).contains(i))
+                    .map(|(skill, _)| skill),
+            );
+            number += 1;
+        }
+
+        Ok(ListResponse {
+            skills,
+            total: total as u32,
+            page,
+            limit,
+            total_pages: total.div_ceil(limit as u64).max(1) as u32,
+        })
+    }
+
+    /// Every skill in a static registry, in page order.
+    async fn read_static_pages(&self) -> Result<Vec<SkillResponse>, ClawdError> {
+        let first = self.read_static_page(1).await?;
+        let total_pages = first.total_pages;
+        let mut skills = first.skills;
+        for number in 2..=total_pages {
+            skills.extend(self.read_static_page(number).await?.skills);
+        }
+        Ok(skills)
+    }
+
+    async fn read_static_page(&self, page: u32) -> Result<ListResponse, ClawdError> {
+        self.read_static(&layout::page(page)).await?.ok_or_else(|| {
+            ClawdError::InvalidResponse(format!("{} has no page {}", self.base_url, page))
+        })
+    }
+
+    /// Reads a document from a static registry, or `None` when there's no
+    /// such file.
+    async fn read_static<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, ClawdError> {
+        let bytes = match &self.backend {
+            Backend::Directory(dir) if !dir.is_dir() => {
+                return Err(ClawdError::InvalidResponse(format!(
+                    "Registry directory {} does not exist",
+                    dir.display()
+                )));
+            }
+            Backend::Directory(dir) => match fs::read(dir.join(path)) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e.into()),
+            },
+            _ => {
+                let url = format!("{}/{}", self.root, path);
+                let response = self.client.get(&url).send().await?;
+                match response.status() {
+                    StatusCode::OK => response.bytes().await?.to_vec(),
+                    StatusCode::NOT_FOUND => return Ok(None),
+                    StatusCode::TOO_MANY_REQUESTS => return Err(ClawdError::RateLimitExceeded),
+                    status => return Err(ClawdError::InvalidResponse(format!("HTTP {}", status))),
+                }
+            }
+        };
+
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| ClawdError::InvalidResponse(format!("{}: {}", path, e)))
+    }
+}
+
+fn check_index_version(document: &IndexDocument) -> Result<(), ClawdError> {
+    if document.version != INDEX_FORMAT_VERSION {
+        return Err(ClawdError::InvalidResponse(format!(
+            "Unsupported index version {}",
+            document.version
+        )));
+    }
+    Ok(())
+}
+
+/// The directory a `file://` URL points at. Relative paths such as
+/// `file://./registry`, which aren't valid file URLs, are taken as written.
+fn file_url_path(url: &str) -> PathBuf {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .unwrap_or_else(|| PathBuf::from(url.trim_start_matches("file:").trim_start_matches("//")))
+}
+
+/// A client that sends `Authorization: Bearer <token>`, or `None` when the
+/// token isn't a valid header value.
+pub(crate) fn authorized_client(token: &str) -> Option<reqwest::Client> {
+    let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token)).ok()?;
+    value.set_sensitive(true);
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::AUTHORIZATION, value);
+    reqwest::Client::builder()
+        .user_agent("clawd-cli")
+        .default_headers(headers)
+        .build()
+        .ok()
 }
@@ -1,9 +1,11 @@
+use crate::api::clawd::authorized_client;
 use crate::error::ClawdError;
+use futures_util::{stream, StreamExt, TryStreamExt};
 use indicatif::ProgressBar;
 use reqwest::StatusCode;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Deserialize)]
 pub struct GitHubContent {
@@ -14,8 +16,39 @@ pub struct GitHubContent {
     pub download_url: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct TreeEntry {
+    pub name: String,
+    pub is_dir: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TreeEntry>,
+}
+
+/// The core REST API quota for this client's (unauthenticated) requests.
+#[derive(Deserialize)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Unix timestamp when the quota resets
+    pub reset: u64,
+}
+
+#[derive(Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimitResources,
+}
+
+#[derive(Deserialize)]
+struct RateLimitResources {
+    core: RateLimit,
+}
+
 pub struct GitHubClient {
     client: reqwest::Client,
+    /// Files downloaded at once
+    parallelism: usize,
 }
 
 impl GitHubClient {
@@ -25,6 +58,35 @@ impl GitHubClient {
                 .user_agent("clawd-cli")
                 .build()
                 .unwrap(),
+            parallelism: 1,
+        }
+    }
+
+    /// Authenticates with `token`, which raises the API rate limit.
+    pub fn with_token(mut self, token: Option<&str>) -> Self {
+        if let Some(client) = token.and_then(authorized_client) {
+            self.client = client;
+        }
+        self
+    }
+
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Checking the rate limit doesn't count against it.
+    pub async fn rate_limit(&self) -> Result<RateLimit, ClawdError> {
+        let response = self
+            .client
+            .get("https://api.github.com/rate_limit")
+            .header("Accept", "application/vnd.github.v3+json")
+            .send()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<RateLimitResponse>().await?.resources.core),
+            status => Err(ClawdError::GitHubError(format!("HTTP {}", status))),
         }
     }
 
@@ -55,6 +117,40 @@ impl GitHubClient {
         }
     }
 
+    /// Recursively lists every file and directory below `path`.
+    pub async fn list_tree(
+        &self,
+        repo: &str,
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> Result<Vec<TreeEntry>, ClawdError> {
+        let contents = self.list_contents(repo, path, git_ref).await?;
+        let mut entries = Vec::new();
+
+        for item in contents {
+            match item.content_type.as_str() {
+                "dir" => {
+                    let children = Box::pin(self.list_tree(repo, &item.path, git_ref)).await?;
+                    entries.push(TreeEntry {
+                        name: item.name,
+                        is_dir: true,
+                        download_url: None,
+                        children,
+                    });
+                }
+                "file" => entries.push(TreeEntry {
+                    name: item.name,
+                    is_dir: false,
+                    download_url: item.download_url,
+                    children: Vec::new(),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(entries)
+    }
+
     pub async fn download_file(&self, download_url: &str) -> Result<Vec<u8>, ClawdError> {
         let response = self.client.get(download_url).send().await?;
         Ok(response.bytes().await?.to_vec())
@@ -68,30 +164,39 @@ impl GitHubClient {
         target_dir: &Path,
         progress: &ProgressBar,
     ) -> Result<(), ClawdError> {
-        let contents = self.list_contents(repo, path, git_ref).await?;
+        let tree = self.list_tree(repo, path, git_ref).await?;
 
-        for item in contents {
-            match item.content_type.as_str() {
-                "file" => {
-                    if let Some(url) = item.download_url {
-                        let file_data = self.download_file(&url).await?;
-                        let file_path = target_dir.join(&item.name);
-                        fs::write(file_path, file_data)?;
-                        progress.inc(1);
-                    }
-                }
-                "dir" => {
-                    let sub_dir = target_dir.join(&item.name);
-                    fs::create_dir_all(&sub_dir)?;
-                    Box::pin(
-                        self.download_directory(repo, &item.path, git_ref, &sub_dir, progress),
-                    )
-                    .await?;
-                }
-                _ => {}
-            }
-        }
+        let mut files = Vec::new();
+        collect_files(&tree, target_dir, &mut files)?;
 
-        Ok(())
+        stream::iter(files)
+            .map(|(file_path, url)| async move {
+                let file_data = self.download_file(&url).await?;
+                fs::write(file_path, file_data)?;
+                progress.inc(1);
+                Ok::<_, ClawdError>(())
+            })
+            .buffer_unordered(self.parallelism)
+            .try_collect::<()>()
+            .await
+    }
+}
+
+/// Creates the directories of `entries` below `dir` and lists the files to
+/// download into them.
+fn collect_files(
+    entries: &[TreeEntry],
+    dir: &Path,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<(), ClawdError> {
+    for entry in entries {
+        let path = dir.join(&entry.name);
+        if entry.is_dir {
+            fs::create_dir_all(&path)?;
+            collect_files(&entry.children, &path, files)?;
+        } else if let Some(url) = &entry.download_url {
+            files.push((path, url.clone()));
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,708 @@
+use crate::api::clawd::{SkillFilters, SkillResponse};
+use crate::api::github::GitHubClient;
+use crate::category::Category;
+use crate::config::{Config, InstallScope, DEFAULT_REGISTRY};
+use crate::download::{self, parse_skill_id, Existing, Target};
+use crate::error::ClawdError;
+use crate::info::strip_frontmatter;
+use crate::list::merge_pages;
+use crate::receipt::InstallReceipt;
+use crate::registry::{self, Registries};
+use crate::remove;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use indicatif::ProgressBar;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const PAGE_SIZE: u32 = 50;
+
+/// An action waiting for the user to press y.
+enum Pending {
+    Install(Vec<String>),
+    Remove(Vec<(String, PathBuf)>),
+}
+
+const HELP: &str = "↑/↓ move  n/p page  / filter  c category  s scope  space mark  \
+                    enter preview  J/K scroll  i install  d remove  q quit";
+
+pub async fn execute_browse(
+    scope_arg: Option<String>,
+    api_url: Option<String>,
+) -> anyhow::Result<()> {
+    let config = Config::new(api_url)?;
+    let scope = match scope_arg {
+        Some(scope_str) => InstallScope::from_str(&scope_str)?,
+        None => config.default_scope.unwrap_or(InstallScope::User),
+    };
+
+    let mut app = Browser::new(config, scope);
+    app.load_page().await?;
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal).await;
+    ratatui::restore();
+
+    result
+}
+
+struct Browser {
+    config: Config,
+    registries: Registries,
+    github: GitHubClient,
+    scope: InstallScope,
+    category: Option<Category>,
+    query: String,
+    editing_query: bool,
+    page: u32,
+    total_pages: u32,
+    total: u32,
+    skills: Vec<SkillResponse>,
+    /// Indices into `skills` that match the text filter
+    visible: Vec<usize>,
+    list_state: ListState,
+    /// Keys, as from `key`, of the marked skills
+    marked: HashSet<String>,
+    /// Keys of the listed skills installed in the current scope
+    installed: HashSet<String>,
+    previews: HashMap<String, String>,
+    preview_scroll: u16,
+    status: String,
+    pending: Option<Pending>,
+}
+
+impl Browser {
+    fn new(config: Config, scope: InstallScope) -> Self {
+        Self {
+            registries: Registries::new(&config),
+            github: config.github_client(),
+            config,
+            scope,
+            category: None,
+            query: String::new(),
+            editing_query: false,
+            page: 1,
+            total_pages: 1,
+            total: 0,
+            skills: Vec::new(),
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            marked: HashSet::new(),
+            installed: HashSet::new(),
+            previews: HashMap::new(),
+            preview_scroll: 0,
+            status: String::new(),
+            pending: None,
+        }
+    }
+
+    async fn run(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if let Some(pending) = self.pending.take() {
+                match (key.code, pending) {
+                    (KeyCode::Char('y'), Pending::Install(ids)) => {
+                        self.install(terminal, ids, true).await?
+                    }
+                    (KeyCode::Char('y'), Pending::Remove(paths)) => self.remove_confirmed(paths),
+                    _ => self.status = "Cancelled".to_string(),
+                }
+                continue;
+            }
+
+            if self.editing_query {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc => self.editing_query = false,
+                    KeyCode::Backspace => {
+                        self.query.pop();
+                        self.apply_filter();
+                    }
+                    KeyCode::Char(c) => {
+                        self.query.push(c);
+                        self.apply_filter();
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+                KeyCode::Char('n') | KeyCode::PageDown if self.page < self.total_pages => {
+                    self.page += 1;
+                    self.reload(terminal).await?;
+                }
+                KeyCode::Char('p') | KeyCode::PageUp if self.page > 1 => {
+                    self.page -= 1;
+                    self.reload(terminal).await?;
+                }
+                KeyCode::Char('/') => self.editing_query = true,
+                KeyCode::Char('c') => {
+                    self.category = next_category(self.category);
+                    self.page = 1;
+                    self.reload(terminal).await?;
+                }
+                KeyCode::Char('s') => {
+                    self.scope = match self.scope {
+                        InstallScope::User => InstallScope::Project,
+                        InstallScope::Project => InstallScope::User,
+                    };
+                    self.refresh_installed();
+                    self.status = format!("Scope: {}", self.scope);
+                }
+                KeyCode::Char(' ') => self.toggle_mark(),
+                KeyCode::Enter => self.load_preview(terminal).await?,
+                KeyCode::Char('J') => self.preview_scroll = self.preview_scroll.saturating_add(3),
+                KeyCode::Char('K') => self.preview_scroll = self.preview_scroll.saturating_sub(3),
+                KeyCode::Char('i') => {
+                    let ids = self.targets();
+                    self.install(terminal, ids, false).await?
+                }
+                KeyCode::Char('d') => self.remove(),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn load_page(&mut self) -> Result<(), ClawdError> {
+        let filters = SkillFilters {
+            category: self.category,
+            ..SkillFilters::default()
+        };
+
+        // Every registry contributes its own page, as in `clawd list`.
+        let mut responses = Vec::new();
+        let mut skipped = Vec::new();
+        let mut last_error = None;
+        for registry in self.registries.iter() {
+            match registry
+                .client
+                .list_skills(self.page, PAGE_SIZE, &filters)
+                .await
+            {
+                Ok(mut response) => {
+                    for skill in &mut response.skills {
+                        skill.registry = Some(registry.name.clone());
+                    }
+                    responses.push(response);
+                }
+                Err(e) => {
+                    skipped.push(registry.name.clone());
+                    last_error = Some(e);
+                }
+            }
+        }
+        if responses.is_empty() {
+            return Err(last_error.expect("at least one registry is configured"));
+        }
+        let response = merge_pages(responses);
+
+        self.total_pages = response.total_pages.max(1);
+        self.total = response.total;
+        self.skills = response
+            .skills
+            .into_iter()
+            .filter(|s| filters.matches(s))
+            .collect();
+        self.apply_filter();
+        self.refresh_installed();
+        self.status = format!(
+            "Page {} of {} ({} skills)",
+            self.page, self.total_pages, self.total
+        );
+        if !skipped.is_empty() {
+            self.status
+                .push_str(&format!(", skipped {}", skipped.join(", ")));
+        }
+
+        Ok(())
+    }
+
+    /// Reloads the current page, keeping the UI up and showing the error in
+    /// the status line if the request fails.
+    async fn reload(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        self.set_busy(terminal, "Loading...")?;
+        if let Err(e) = self.load_page().await {
+            self.status = format!("Error: {}", e);
+        }
+        Ok(())
+    }
+
+    fn set_busy(&mut self, terminal: &mut DefaultTerminal, message: &str) -> anyhow::Result<()> {
+        self.status = message.to_string();
+        terminal.draw(|frame| self.draw(frame))?;
+        Ok(())
+    }
+
+    fn apply_filter(&mut self) {
+        let query = self.query.to_lowercase();
+        self.visible = self
+            .skills
+            .iter()
+            .enumerate()
+            .filter(|(_, skill)| query.is_empty() || matches_text(skill, &query))
+            .map(|(i, _)| i)
+            .collect();
+
+        let selected = if self.visible.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.list_state.select(selected);
+        self.preview_scroll = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, self.visible.len() as isize - 1);
+        self.list_state.select(Some(next as usize));
+        self.preview_scroll = 0;
+    }
+
+    fn selected(&self) -> Option<&SkillResponse> {
+        let index = *self.visible.get(self.list_state.selected()?)?;
+        self.skills.get(index)
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(key) = self.selected().map(key) else {
+            return;
+        };
+        if !self.marked.remove(&key) {
+            self.marked.insert(key);
+        }
+        self.move_selection(1);
+    }
+
+    /// Keys of the marked skills, or of the selected one when nothing is
+    /// marked.
+    fn targets(&self) -> Vec<String> {
+        if self.marked.is_empty() {
+            self.selected().map(key).into_iter().collect()
+        } else {
+            let mut ids: Vec<String> = self.marked.iter().cloned().collect();
+            ids.sort();
+            ids
+        }
+    }
+
+    /// Finds the listed skills installed in the current scope by their
+    /// receipts, as `clawd remove` does.
+    fn refresh_installed(&mut self) {
+        let Ok(skills_dir) = self.config.skills_dir(self.scope) else {
+            self.installed.clear();
+            return;
+        };
+        let installed = InstallReceipt::installed_in(&skills_dir);
+        self.installed = self
+            .skills
+            .iter()
+            .filter(|skill| {
+                remove::find_installed(
+                    &self.config,
+                    &skills_dir,
+                    &installed,
+                    skill.registry.as_deref(),
+                    &skill.id,
+                )
+                .is_some()
+            })
+            .map(key)
+            .collect();
+    }
+
+    /// Installs the skills keyed `keys` and their dependencies the way
+    /// `clawd add` does. Asks first, by setting `pending`, when that would
+    /// overwrite something and the user hasn't `confirmed` yet.
+    async fn install(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        keys: Vec<String>,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        // Plan every skill before installing any, so one that can't be
+        // installed leaves the others untouched too.
+        let mut targets: Vec<Target> = Vec::new();
+        for key in &keys {
+            let (_, id) = registry::split_registry(key);
+            self.set_busy(terminal, &format!("Resolving {}...", id))?;
+            match self.plan(key).await {
+                Ok(plan) => {
+                    for target in plan {
+                        if !targets.iter().any(|t| t.step.id == target.step.id) {
+                            targets.push(target);
+                        }
+                    }
+                }
+                Err(e) => {
+                    self.status = format!("Cannot install {}: {}", id, e);
+                    return Ok(());
+                }
+            }
+        }
+        targets.retain(|t| !t.up_to_date);
+
+        let overwrites: Vec<String> = targets
+            .iter()
+            .filter(|t| t.needs_confirmation())
+            .map(|t| t.path.display().to_string())
+            .collect();
+        if !confirmed && !overwrites.is_empty() {
+            self.status = format!("Overwrite {}? (y/n)", overwrites.join(", "));
+            self.pending = Some(Pending::Install(keys));
+            return Ok(());
+        }
+
+        let mut installed = 0;
+        for target in &targets {
+            self.set_busy(terminal, &format!("Installing {}...", target.step.id))?;
+            if let Err(e) = self.install_one(target).await {
+                self.status = format!("Failed to install {}: {}", target.step.id, e);
+                self.refresh_installed();
+                return Ok(());
+            }
+            installed += 1;
+        }
+
+        self.marked.clear();
+        self.refresh_installed();
+        self.status = format!("Installed {} skill(s) into {} scope", installed, self.scope);
+        Ok(())
+    }
+
+    /// The install plan for the skill keyed `key`, from the registry it was
+    /// listed in and held to the same policy and requirements as `clawd add`
+    /// without `--force`.
+    async fn plan(&self, key: &str) -> Result<Vec<Target>, ClawdError> {
+        let (registry, skill_id) = registry::split_registry(key);
+        let targets = download::plan_install(
+            &self.config,
+            &self.registries,
+            registry,
+            skill_id,
+            None,
+            None,
+            self.scope,
+        )
+        .await?;
+
+        for target in targets.iter().filter(|t| !t.up_to_date) {
+            if let Existing::Other(installed) = &target.existing {
+                return Err(ClawdError::InstallCollision(
+                    target.path.display().to_string(),
+                    installed.clone(),
+                ));
+            }
+        }
+
+        let unmet = download::requirement_checks(&targets)
+            .iter()
+            .filter(|(_, check)| !check.is_met())
+            .count();
+        if unmet > 0 && !self.config.ignore_requirements {
+            return Err(ClawdError::RequirementsNotMet(unmet));
+        }
+        Ok(targets)
+    }
+
+    async fn install_one(&self, target: &Target) -> Result<(), ClawdError> {
+        let source = download::fetch_source(&self.registries, &target.step).await?;
+        download::install_target(&self.github, target, &source, &ProgressBar::hidden()).await?;
+        Ok(())
+    }
+
+    /// Asks before removing the installed targets, naming installed skills
+    /// that depend on them.
+    fn remove(&mut self) {
+        let skills_dir = match self.config.skills_dir(self.scope) {
+            Ok(dir) => dir,
+            Err(e) => {
+                self.status = format!("Cannot remove: {}", e);
+                return;
+            }
+        };
+        let installed = InstallReceipt::installed_in(&skills_dir);
+        let targets = self.targets();
+        let ids: Vec<&str> = targets
+            .iter()
+            .map(|key| registry::split_registry(key).1)
+            .collect();
+
+        let mut paths = Vec::new();
+        let mut dependents = Vec::new();
+        let mut unmanaged = Vec::new();
+        for key in &targets {
+            let (registry, id) = registry::split_registry(key);
+            let Some(path) =
+                remove::find_installed(&self.config, &skills_dir, &installed, registry, id)
+            else {
+                continue;
+            };
+            for dependent in remove::dependents(&installed, id) {
+                if !ids.contains(&dependent.as_str()) && !dependents.contains(&dependent) {
+                    dependents.push(dependent);
+                }
+            }
+            if InstallReceipt::read(&path).is_none() {
+                unmanaged.push(path.display().to_string());
+            }
+            paths.push((id.to_string(), path));
+        }
+
+        if paths.is_empty() {
+            self.status = format!("Not installed in {} scope", self.scope);
+            return;
+        }
+        let names: Vec<&str> = paths.iter().map(|(id, _)| id.as_str()).collect();
+        let mut warnings = Vec::new();
+        if !unmanaged.is_empty() {
+            warnings.push(format!(
+                "{} unmanaged (no clawd receipt)",
+                unmanaged.join(", ")
+            ));
+        }
+        if !dependents.is_empty() {
+            warnings.push(format!(
+                "{} required by {}",
+                names.join(", "),
+                dependents.join(", ")
+            ));
+        }
+        self.status = if warnings.is_empty() {
+            format!("Remove {}? (y/n)", names.join(", "))
+        } else {
+            format!("{}. Remove anyway? (y/n)", warnings.join("; "))
+        };
+        self.pending = Some(Pending::Remove(paths));
+    }
+
+    fn remove_confirmed(&mut self, paths: Vec<(String, PathBuf)>) {
+        let mut removed = 0;
+        for (id, path) in &paths {
+            if let Err(e) = fs::remove_dir_all(path) {
+                self.status = format!("Failed to remove {}: {}", id, e);
+                self.refresh_installed();
+                return;
+            }
+            removed += 1;
+        }
+
+        self.marked.clear();
+        self.refresh_installed();
+        self.status = format!("Removed {} skill(s) from {} scope", removed, self.scope);
+    }
+
+    async fn load_preview(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        let Some(key) = self.selected().map(key) else {
+            return Ok(());
+        };
+        if self.previews.contains_key(&key) {
+            return Ok(());
+        }
+
+        let (_, id) = registry::split_registry(&key);
+        self.set_busy(terminal, &format!("Loading preview for {}...", id))?;
+        match self.fetch_preview(&key).await {
+            Ok(preview) => {
+                self.previews.insert(key, preview);
+                self.status.clear();
+            }
+            Err(e) => self.status = format!("Could not load preview: {}", e),
+        }
+        Ok(())
+    }
+
+    /// Fetches README.mdx from the registry the skill keyed `key` was listed
+    /// in, falling back to the skill's SKILL.md on GitHub.
+    async fn fetch_preview(&self, key: &str) -> Result<String, ClawdError> {
+        let (registry, skill_id) = registry::split_registry(key);
+        let (author, name) = parse_skill_id(skill_id)?;
+        let (registry, detail) = self.registries.get_skill(registry, &author, &name).await?;
+        if let Some(readme) = detail.readme {
+            return Ok(strip_frontmatter(&readme).to_string());
+        }
+
+        let download_info = registry.client.get_download_info(&author, &name).await?;
+        let contents = self
+            .github
+            .list_contents(
+                &download_info.repo,
+                &download_info.path,
+                download_info.git_ref.as_deref(),
+            )
+            .await?;
+
+        let skill_md = contents
+            .iter()
+            .find(|c| c.name == "SKILL.md")
+            .and_then(|c| c.download_url.as_deref());
+
+        match skill_md {
+            Some(url) => {
+                let bytes = self.github.download_file(url).await?;
+                Ok(strip_frontmatter(&String::from_utf8_lossy(&bytes)).to_string())
+            }
+            None => Ok("No README or SKILL.md found.".to_string()),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .areas(body);
+
+        let category = self.category.map(|c| c.as_str()).unwrap_or("All");
+        let filter = if self.editing_query {
+            format!("{}▏", self.query)
+        } else if self.query.is_empty() {
+            "-".to_string()
+        } else {
+            self.query.clone()
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("clawd browse", Style::new().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(
+                    "  Category: {}  Scope: {}  Filter: {}",
+                    category, self.scope, filter
+                )),
+            ])),
+            header,
+        );
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&i| {
+                let skill = &self.skills[i];
+                let key = key(skill);
+                let mark = if self.marked.contains(&key) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                let installed = if self.installed.contains(&key) {
+                    Span::styled("✓ ", Style::new().fg(Color::Green))
+                } else {
+                    Span::raw("  ")
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", mark)),
+                    installed,
+                    Span::raw(display_id(skill)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                " Skills (page {}/{}) ",
+                self.page, self.total_pages
+            )))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let preview = match self.selected() {
+            Some(skill) => {
+                let mut text = format!(
+                    "{}\n{}\n\nCategory: {}\nTags: {}\nDownloads: {}\n\n{}\n",
+                    skill.title,
+                    display_id(skill),
+                    skill.category,
+                    skill.tags.join(", "),
+                    skill.download_count,
+                    skill.description
+                );
+                match self.previews.get(&key(skill)) {
+                    Some(preview) => {
+                        text.push('\n');
+                        text.push_str(preview);
+                    }
+                    None => text.push_str("\nPress Enter to load the README."),
+                }
+                text
+            }
+            None => "No skills match.".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(preview)
+                .block(Block::default().borders(Borders::ALL).title(" Preview "))
+                .wrap(Wrap { trim: false })
+                .scroll((self.preview_scroll, 0)),
+            preview_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(self.status.as_str()),
+                Line::styled(HELP, Style::new().fg(Color::DarkGray)),
+            ]),
+            footer,
+        );
+    }
+}
+
+/// Identifies a listed skill by its registry and ID, so the same ID listed
+/// by two registries is installed from the one the user picked.
+fn key(skill: &SkillResponse) -> String {
+    format!(
+        "{}:{}",
+        skill.registry.as_deref().unwrap_or(DEFAULT_REGISTRY),
+        skill.id
+    )
+}
+
+fn display_id(skill: &SkillResponse) -> String {
+    match &skill.registry {
+        Some(registry) => registry::qualified_id(registry, &skill.id),
+        None => skill.id.clone(),
+    }
+}
+
+fn matches_text(skill: &SkillResponse, query: &str) -> bool {
+    skill.id.to_lowercase().contains(query)
+        || skill.title.to_lowercase().contains(query)
+        || skill.description.to_lowercase().contains(query)
+        || skill.tags.iter().any(|t| t.to_lowercase().contains(query))
+}
+
+fn next_category(current: Option<Category>) -> Option<Category> {
+    match current {
+        None => Some(Category::ALL[0]),
+        Some(category) => {
+            let index = Category::ALL.iter().position(|c| *c == category)?;
+            Category::ALL.get(index + 1).copied()
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use crate::api::clawd::{
+    layout, Author, DownloadResponse, ListResponse, Requirements, SkillDetailResponse,
+    SkillResponse, INDEX_FORMAT_VERSION, STATIC_PAGE_SIZE,
+};
+use crate::error::ClawdError;
+use crate::info::strip_frontmatter;
+use crate::output::OutputFormat;
+use crate::validate::{self, Frontmatter, Problem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The README.mdx frontmatter fields that make up a registry entry.
+#[derive(Deserialize)]
+struct Entry {
+    id: String,
+    title: String,
+    description: String,
+    version: Option<String>,
+    category: String,
+    author: String,
+    #[serde(default)]
+    contributors: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    requirements: Option<Requirements>,
+    license: Option<String>,
+    repo: String,
+    path: String,
+    #[serde(rename = "ref")]
+    git_ref: Option<String>,
+    #[serde(default, rename = "isVerified")]
+    is_verified: bool,
+}
+
+/// A skill ready to be written out.
+struct Built {
+    detail: SkillDetailResponse,
+    download: DownloadResponse,
+}
+
+/// The index document, as `ClawdClient::get_index` reads it.
+#[derive(Serialize)]
+struct IndexDocument<'a> {
+    version: u32,
+    skills: Vec<IndexEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    #[serde(flatten)]
+    skill: &'a SkillResponse,
+    version: &'a Option<String>,
+    license: &'a Option<String>,
+    contributors: &'a [String],
+    created_at: &'a Option<String>,
+    requirements: &'a Option<Requirements>,
+    #[serde(flatten)]
+    download: &'a DownloadResponse,
+}
+
+#[derive(Serialize)]
+struct BuildDocument {
+    skills: usize,
+    pages: u32,
+    out_dir: PathBuf,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    problems: Vec<Problem>,
+}
+
+/// Turns a `skills/<author>/<name>/README.mdx` tree and the `authors.json`
+/// next to it into the files of a static registry in `out_dir` (see
+/// `api::clawd::layout`). Nothing is written if any entry is invalid.
+pub fn execute_registry_build(
+    skills_dir: PathBuf,
+    out_dir: PathBuf,
+    authors: Option<PathBuf>,
+    output: OutputFormat,
+) -> Result<()> {
+    let canonical = skills_dir
+        .canonicalize()
+        .with_context(|| format!("Cannot access {}", skills_dir.display()))?;
+    let authors_path = match authors {
+        Some(path) => path,
+        None => canonical
+            .parent()
+            .map(|root| root.join("authors.json"))
+            .context("The skills directory has no parent to find authors.json in")?,
+    };
+    let authors: BTreeMap<String, Author> = validate::read_json(&authors_path)?;
+
+    // Hold entries to the same checks as `clawd validate`, so the registry
+    // never serves one a PR couldn't merge.
+    let mut problems = validate::check_skills_dir(&canonical, &authors_path)?;
+    let mut skills = Vec::new();
+    if problems.is_empty() {
+        for author_dir in validate::sorted_subdirs(&skills_dir)? {
+            for dir in validate::sorted_subdirs(&author_dir)? {
+                if let Some(skill) = build_skill(&skills_dir, &dir, &authors, &mut problems) {
+                    skills.push(skill);
+                }
+            }
+        }
+    }
+
+    let total = skills.len() as u32;
+    let pages = total.div_ceil(STATIC_PAGE_SIZE).max(1);
+    let document = BuildDocument {
+        skills: skills.len(),
+        pages,
+        out_dir: out_dir.clone(),
+        problems,
+    };
+
+    if !document.problems.is_empty() {
+        let count = document.problems.len();
+        if output.is_structured() {
+            output.print(&document)?;
+        } else {
+            for problem in &document.problems {
+                println!("{}", problem);
+            }
+            println!();
+        }
+        return Err(ClawdError::ValidationFailed(count).into());
+    }
+
+    // Start from scratch so skills removed from the tree disappear too.
+    let api_dir = out_dir.join("api");
+    if api_dir.exists() {
+        fs::remove_dir_all(&api_dir)
+            .with_context(|| format!("Cannot clear {}", api_dir.display()))?;
+    }
+
+    for (i, chunk) in skills.chunks(STATIC_PAGE_SIZE as usize).enumerate() {
+        let page = ListResponse {
+            skills: chunk.iter().map(|s| s.detail.skill.clone()).collect(),
+            total,
+            page: i as u32 + 1,
+            limit: STATIC_PAGE_SIZE,
+            total_pages: pages,
+        };
+        write_json(&out_dir, &layout::page(page.page), &page)?;
+    }
+    // A registry without skills still lists an empty first page.
+    if skills.is_empty() {
+        let page = ListResponse {
+            skills: Vec::new(),
+            total: 0,
+            page: 1,
+            limit: STATIC_PAGE_SIZE,
+            total_pages: 1,
+        };
+        write_json(&out_dir, &layout::page(1), &page)?;
+    }
+
+    for skill in &skills {
+        let (author, name) = skill
+            .detail
+            .skill
+            .id
+            .split_once('/')
+            .expect("checked when the entry was read");
+        write_json(&out_dir, &layout::skill(author, name), &skill.detail)?;
+        write_json(&out_dir, &layout::download(author, name), &skill.download)?;
+    }
+
+    let index = IndexDocument {
+        version: INDEX_FORMAT_VERSION,
+        skills: skills
+            .iter()
+            .map(|s| IndexEntry {
+                skill: &s.detail.skill,
+                version: &s.detail.version,
+                license: &s.detail.license,
+                contributors: &s.detail.contributors,
+                created_at: &s.detail.created_at,
+                requirements: &s.detail.requirements,
+                download: &s.download,
+            })
+            .collect(),
+    };
+    write_json(&out_dir, layout::INDEX, &index)?;
+
+    output.status(format_args!(
+        "✅ Built {} skills into {} ({} list page(s))",
+        skills.len(),
+        out_dir.display(),
+        pages
+    ));
+    output.print(&document)?;
+    Ok(())
+}
+
+/// Reads the entry in `dir`, recording problems instead of failing so every
+/// broken entry is reported at once.
+fn build_skill(
+    skills_dir: &Path,
+    dir: &Path,
+    authors: &BTreeMap<String, Author>,
+    problems: &mut Vec<Problem>,
+) -> Option<Built> {
+    let readme_path = dir.join("README.mdx");
+    let mut problem = |line: Option<usize>, message: String| {
+        problems.push(Problem {
+            file: readme_path.clone(),
+            line,
+            message,
+        });
+    };
+
+    let contents = match fs::read_to_string(&readme_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            problem(None, format!("cannot read file: {}", e));
+            return None;
+        }
+    };
+    let frontmatter = match Frontmatter::parse(&contents) {
+        Ok(frontmatter) => frontmatter,
+        Err((line, message)) => {
+            problem(Some(line), message);
+            return None;
+        }
+    };
+    let entry: Entry = match serde_json::from_value(frontmatter.data.clone()) {
+        Ok(entry) => entry,
+        Err(e) => {
+            problem(None, format!("invalid frontmatter: {}", e));
+            return None;
+        }
+    };
+
+    let expected_id = dir
+        .strip_prefix(skills_dir)
+        .unwrap_or(dir)
+        .to_string_lossy()
+        .replace('\\', "/");
+    if entry.id != expected_id {
+        problem(
+            Some(frontmatter.line_of(&["id"])),
+            format!(
+                "id '{}' does not match the directory layout, expected '{}'",
+                entry.id, expected_id
+            ),
+        );
+        return None;
+    }
+    let Some(author) = authors.get(&entry.author) else {
+        problem(
+            Some(frontmatter.line_of(&["author"])),
+            format!("author '{}' is not listed in authors.json", entry.author),
+        );
+        return None;
+    };
+
+    let body = strip_frontmatter(&contents).trim();
+    Some(Built {
+        detail: SkillDetailResponse {
+            skill: SkillResponse {
+                id: entry.id,
+                title: entry.title,
+                description: entry.description,
+                category: entry.category,
+                tags: entry.tags,
+                author: Some(author.clone()),
+                download_count: 0,
+                rating: 0.0,
+                is_verified: entry.is_verified,
+                updated_at: entry.updated_at,
+                registry: None,
+            },
+            version: entry.version,
+            license: entry.license,
+            contributors: entry.contributors,
+            created_at: entry.created_at,
+            requirements: entry.requirements,
+            readme: (!body.is_empty()).then(|| body.to_string()),
+        },
+        download: DownloadResponse {
+            repo: entry.repo,
+            path: entry.path,
+            git_ref: entry.git_ref,
+        },
+    })
+}
+
+fn write_json<T: Serialize>(out_dir: &Path, path: &str, document: &T) -> Result<()> {
+    let path = out_dir.join(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Cannot create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string(document)?;
+    fs::write(&path, json).with_context(|| format!("Cannot write {}", path.display()))
+}
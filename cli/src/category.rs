@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+/// Skill categories allowed by `readme-schema.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+pub enum Category {
+    #[value(name = "development")]
+    Development,
+    #[serde(rename = "DevOps")]
+    #[value(name = "devops")]
+    DevOps,
+    #[value(name = "writing")]
+    Writing,
+    #[serde(rename = "QA")]
+    #[value(name = "qa")]
+    Qa,
+    #[value(name = "security")]
+    Security,
+    #[value(name = "data")]
+    Data,
+    #[value(name = "design")]
+    Design,
+    #[value(name = "other")]
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 8] = [
+        Self::Development,
+        Self::DevOps,
+        Self::Writing,
+        Self::Qa,
+        Self::Security,
+        Self::Data,
+        Self::Design,
+        Self::Other,
+    ];
+
+    /// Parses the exact spelling used in frontmatter, e.g. `DevOps`.
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Development => "Development",
+            Self::DevOps => "DevOps",
+            Self::Writing => "Writing",
+            Self::Qa => "QA",
+            Self::Security => "Security",
+            Self::Data => "Data",
+            Self::Design => "Design",
+            Self::Other => "Other",
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn matches_readme_schema() {
+        let schema: Value = serde_json::from_str(include_str!("../../readme-schema.json")).unwrap();
+        let allowed: Vec<&str> = schema["properties"]["category"]["enum"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        let ours: Vec<&str> = Category::ALL.iter().map(Category::as_str).collect();
+        assert_eq!(allowed, ours);
+    }
+
+    #[test]
+    fn from_str_takes_frontmatter_spelling() {
+        assert_eq!(Category::from_str("DevOps"), Some(Category::DevOps));
+        assert_eq!(Category::from_str("QA"), Some(Category::Qa));
+        assert_eq!(Category::from_str("devops"), None);
+        assert_eq!(Category::from_str("Testing"), None);
+    }
+}
@@ -1,9 +1,25 @@
+use crate::api::github::GitHubClient;
 use crate::error::ClawdError;
-use anyhow::{Context, Result};
+use crate::settings::{self, LoadedSettings};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub const DEFAULT_API_URL: &str = "https://api.clawd.xyz";
+
+/// Name of the public registry at `api_url`.
+pub const DEFAULT_REGISTRY: &str = "clawd";
+
+/// Priority of the public registry unless configured, so declared
+/// registries (priority 0 by default) are tried before it.
+pub const DEFAULT_REGISTRY_PRIORITY: i64 = 100;
+
+/// Files downloaded at once when `parallelism` isn't configured.
+pub const DEFAULT_PARALLELISM: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum InstallScope {
     User,
     Project,
@@ -32,29 +48,138 @@ impl std::fmt::Display for InstallScope {
     }
 }
 
+/// How install directories are named when no `--as` alias is given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum NamingStrategy {
+    /// `skill-name`
+    #[default]
+    Name,
+    /// `author-skill-name`, which never collides across authors
+    AuthorName,
+}
+
+impl NamingStrategy {
+    pub fn dir_name(&self, author: &str, name: &str) -> String {
+        match self {
+            Self::Name => name.to_string(),
+            Self::AuthorName => format!("{}-{}", author, name),
+        }
+    }
+}
+
+/// A registry skills are looked up in.
+pub struct RegistryConfig {
+    pub name: String,
+    pub url: String,
+    pub token: Option<String>,
+    pub priority: i64,
+}
+
 pub struct Config {
     pub api_url: String,
+    pub github_token: Option<String>,
     pub install_base_path: PathBuf,
+    pub cache_dir: PathBuf,
+    pub naming: NamingStrategy,
+    /// Scope to install into when none is given, instead of asking
+    pub default_scope: Option<InstallScope>,
+    pub parallelism: usize,
+    pub verified_only: bool,
+    pub ignore_requirements: bool,
+    /// Every registry, in the order unqualified skill IDs are looked up
+    pub registries: Vec<RegistryConfig>,
 }
 
 impl Config {
+    /// Builds the configuration from defaults, the config files and the
+    /// environment (see `settings`), with `api_url` from the command line
+    /// taking precedence over all of them.
     pub fn new(api_url: Option<String>) -> Result<Self> {
+        let mut settings = LoadedSettings::load()?.settings();
+
+        // `[registries.clawd]` configures the public registry, below the
+        // top-level `api_url` and `api_token`.
+        let public = settings.registries.remove(DEFAULT_REGISTRY);
         let api_url = api_url
-            .or_else(|| env::var("CLAWD_API_URL").ok())
-            .unwrap_or_else(|| "https://api.clawd.xyz".to_string());
+            .or(settings.api_url)
+            .or_else(|| public.as_ref().and_then(|r| r.url.clone()))
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let api_token = settings.api_token.or_else(|| {
+            public
+                .as_ref()
+                .and_then(|r| registry_token(r.token_env.as_deref(), r.token.clone()))
+        });
+
+        let mut registries = vec![RegistryConfig {
+            name: DEFAULT_REGISTRY.to_string(),
+            url: api_url.clone(),
+            token: api_token,
+            priority: public
+                .and_then(|r| r.priority)
+                .unwrap_or(DEFAULT_REGISTRY_PRIORITY),
+        }];
+        for (name, registry) in settings.registries {
+            if !settings::is_valid_registry_name(&name) {
+                bail!(
+                    "Invalid registry name '{}'. Use lowercase letters, digits and hyphens",
+                    name
+                );
+            }
+            let Some(url) = registry.url else {
+                bail!("registries.{}.url is not set", name);
+            };
+            registries.push(RegistryConfig {
+                token: registry_token(registry.token_env.as_deref(), registry.token),
+                priority: registry.priority.unwrap_or(0),
+                name,
+                url,
+            });
+        }
+        // Ties go by name so the lookup order never depends on file order.
+        registries.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.name.cmp(&b.name)));
 
-        let home = directories::UserDirs::new().context("Could not find home directory")?;
-        let install_base_path = home.home_dir().join(".claude/skills");
+        let install_base_path = match settings.skills_dir {
+            Some(dir) => settings::expand_home(dir),
+            None => default_skills_dir()?,
+        };
+        let cache_dir = match settings.cache_dir {
+            Some(dir) => settings::expand_home(dir),
+            None => default_cache_dir()?,
+        };
 
         Ok(Self {
             api_url,
+            github_token: settings.github_token,
             install_base_path,
+            cache_dir,
+            naming: settings.naming.unwrap_or_default(),
+            default_scope: settings.scope,
+            parallelism: settings.parallelism.unwrap_or(DEFAULT_PARALLELISM).max(1),
+            verified_only: settings.policy.verified_only.unwrap_or(false),
+            ignore_requirements: settings.policy.ignore_requirements.unwrap_or(false),
+            registries,
         })
     }
 
+    pub fn github_client(&self) -> GitHubClient {
+        GitHubClient::new()
+            .with_token(self.github_token.as_deref())
+            .with_parallelism(self.parallelism)
+    }
+
+    /// Directory name for a skill: `alias` when given, otherwise per the
+    /// configured naming strategy.
+    pub fn install_dir_name(&self, author: &str, name: &str, alias: Option<&str>) -> String {
+        match alias {
+            Some(alias) => alias.to_string(),
+            None => self.naming.dir_name(author, name),
+        }
+    }
+
     pub fn resolve_install_path(
         &self,
-        skill_name: &str,
+        dir_name: &str,
         custom_path: Option<PathBuf>,
         scope: InstallScope,
     ) -> Result<PathBuf, ClawdError> {
@@ -62,30 +187,43 @@ impl Config {
             return Ok(path);
         }
 
+        Ok(self.skills_dir(scope)?.join(dir_name))
+    }
+
+    /// The directory skills of `scope` are installed into.
+    pub fn skills_dir(&self, scope: InstallScope) -> Result<PathBuf, ClawdError> {
         match scope {
-            InstallScope::User => Ok(self.install_base_path.join(skill_name)),
-            InstallScope::Project => Self::find_project_skills_dir()?
-                .map(|p| p.join(skill_name))
-                .ok_or(ClawdError::NoProjectRoot),
+            InstallScope::User => Ok(self.install_base_path.clone()),
+            InstallScope::Project => {
+                Self::find_project_skills_dir().ok_or(ClawdError::NoProjectRoot)
+            }
         }
     }
 
-    fn find_project_skills_dir() -> Result<Option<PathBuf>, ClawdError> {
-        let current_dir = std::env::current_dir().map_err(ClawdError::FileSystemError)?;
-
-        let mut path = current_dir.as_path();
-        loop {
-            let claude_dir = path.join(".claude");
-            if claude_dir.exists() && claude_dir.is_dir() {
-                return Ok(Some(claude_dir.join("skills")));
-            }
+    fn find_project_skills_dir() -> Option<PathBuf> {
+        settings::find_project_dir().map(|dir| dir.join("skills"))
+    }
+}
 
-            match path.parent() {
-                Some(parent) => path = parent,
-                None => break,
-            }
-        }
+/// The user-scope skills directory when `skills_dir` isn't configured.
+pub fn default_skills_dir() -> Result<PathBuf> {
+    let home = directories::UserDirs::new().context("Could not find home directory")?;
+    Ok(home.home_dir().join(".claude/skills"))
+}
 
-        Ok(None)
+/// Where the index is cached when `cache_dir` isn't configured.
+pub fn default_cache_dir() -> Result<PathBuf> {
+    if let Some(dirs) = directories::ProjectDirs::from("", "", "clawd") {
+        return Ok(dirs.cache_dir().to_path_buf());
     }
+    let home = directories::UserDirs::new().context("Could not find home directory")?;
+    Ok(home.home_dir().join(".cache/clawd"))
+}
+
+/// The token in the `token_env` variable when it's set, otherwise `token`.
+fn registry_token(token_env: Option<&str>, token: Option<String>) -> Option<String> {
+    token_env
+        .and_then(|name| env::var(name).ok())
+        .filter(|token| !token.is_empty())
+        .or(token)
 }
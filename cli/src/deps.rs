@@ -0,0 +1,305 @@
+use crate::api::clawd::SkillDetailResponse;
+use crate::download::parse_skill_id;
+use crate::error::ClawdError;
+use crate::registry::{self, Registries};
+use semver::{Version, VersionReq};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+
+/// A dependency on another skill, declared in `requirements.dependencies` as
+/// `author/name` or `author/name@<range>`, e.g. `obra/writing-plans@^1.0`.
+/// A `registry:` prefix pins it to one configured registry.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub registry: Option<String>,
+    pub id: String,
+    pub req: Option<VersionReq>,
+}
+
+impl Dependency {
+    pub fn parse(spec: &str) -> Result<Self, ClawdError> {
+        let spec = spec.trim();
+        let (id, req) = match spec.split_once('@') {
+            Some((id, req)) => (id.trim(), Some(req.trim())),
+            None => (spec, None),
+        };
+        let (registry, id) = registry::split_registry(id);
+
+        parse_skill_id(id)
+            .map_err(|e| ClawdError::InvalidDependency(spec.to_string(), e.to_string()))?;
+        let req = req
+            .map(VersionReq::parse)
+            .transpose()
+            .map_err(|e| ClawdError::InvalidDependency(spec.to_string(), e.to_string()))?;
+
+        Ok(Self {
+            registry: registry.map(str::to_string),
+            id: id.to_string(),
+            req,
+        })
+    }
+
+    /// Whether `version` satisfies the range. A skill without a parseable
+    /// version only satisfies dependencies without one.
+    pub fn matches(&self, version: Option<&str>) -> bool {
+        let Some(req) = &self.req else {
+            return true;
+        };
+        version
+            .and_then(|v| Version::parse(v.trim_start_matches('v')).ok())
+            .is_some_and(|v| req.matches(&v))
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(registry) = &self.registry {
+            write!(f, "{}:", registry)?;
+        }
+        match &self.req {
+            Some(req) => write!(f, "{}@{}", self.id, req),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// A skill in an install plan.
+pub struct PlanStep {
+    pub id: String,
+    /// The registry the skill resolved from
+    pub registry: String,
+    pub detail: SkillDetailResponse,
+    pub dependencies: Vec<Dependency>,
+    /// The skill that pulled this one into the plan; `None` for the skill
+    /// that was asked for.
+    pub required_by: Option<String>,
+}
+
+/// Resolves `root` and everything it transitively depends on into an install
+/// plan, ordered so every skill comes after its dependencies. Skills without
+/// a registry are looked up in priority order, `root` included.
+pub async fn resolve(
+    registries: &Registries,
+    root_registry: Option<&str>,
+    root: &str,
+) -> Result<Vec<PlanStep>, ClawdError> {
+    let mut nodes: BTreeMap<String, (SkillDetailResponse, Vec<Dependency>)> = BTreeMap::new();
+    let mut required_by: BTreeMap<String, String> = BTreeMap::new();
+    let mut queue = VecDeque::from([(root_registry.map(str::to_string), root.to_string())]);
+
+    while let Some((registry, id)) = queue.pop_front() {
+        if nodes.contains_key(&id) {
+            continue;
+        }
+
+        let (author, name) = parse_skill_id(&id)?;
+        let detail = match registries
+            .get_skill(registry.as_deref(), &author, &name)
+            .await
+        {
+            Ok((_, detail)) => detail,
+            Err(ClawdError::SkillNotFound(_)) if id != root => {
+                return Err(ClawdError::UnsatisfiedDependency(format!(
+                    "{} requires {}, which is not in {}",
+                    required_by[&id],
+                    id,
+                    match &registry {
+                        Some(registry) => format!("registry {}", registry),
+                        None => "any registry".to_string(),
+                    }
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let dependencies = detail
+            .requirements
+            .iter()
+            .flat_map(|r| &r.dependencies)
+            .map(|spec| Dependency::parse(spec))
+            .collect::<Result<Vec<_>, _>>()?;
+        for dep in &dependencies {
+            required_by
+                .entry(dep.id.clone())
+                .or_insert_with(|| id.clone());
+            queue.push_back((dep.registry.clone(), dep.id.clone()));
+        }
+
+        nodes.insert(id, (detail, dependencies));
+    }
+
+    for (id, (_, dependencies)) in &nodes {
+        for dep in dependencies {
+            let detail = &nodes[&dep.id].0;
+            let version = detail.version.as_deref();
+            let found_in = detail.skill.registry.as_deref().unwrap_or_default();
+            if let Some(registry) = dep.registry.as_deref().filter(|r| *r != found_in) {
+                return Err(ClawdError::UnsatisfiedDependency(format!(
+                    "{} requires {} from {}, but it was resolved from {}",
+                    id, dep.id, registry, found_in
+                )));
+            }
+            if !dep.matches(version) {
+                return Err(ClawdError::UnsatisfiedDependency(format!(
+                    "{} requires {}, but the registry has version {}",
+                    id,
+                    dep,
+                    version.unwrap_or("(none)")
+                )));
+            }
+        }
+    }
+
+    let mut order = Vec::new();
+    visit(
+        root,
+        &nodes,
+        &mut Vec::new(),
+        &mut HashSet::new(),
+        &mut order,
+    )?;
+
+    Ok(order
+        .into_iter()
+        .map(|id| {
+            let (detail, dependencies) = nodes.remove(&id).expect("resolved above");
+            PlanStep {
+                required_by: (id != root).then(|| required_by[&id].clone()),
+                registry: detail.skill.registry.clone().unwrap_or_default(),
+                id,
+                detail,
+                dependencies,
+            }
+        })
+        .collect())
+}
+
+/// Depth-first post-order walk. `stack` holds the current path so a skill
+/// seen twice on it means a cycle.
+fn visit(
+    id: &str,
+    nodes: &BTreeMap<String, (SkillDetailResponse, Vec<Dependency>)>,
+    stack: &mut Vec<String>,
+    done: &mut HashSet<String>,
+    order: &mut Vec<String>,
+) -> Result<(), ClawdError> {
+    if done.contains(id) {
+        return Ok(());
+    }
+    if let Some(start) = stack.iter().position(|s| s == id) {
+        let mut cycle = stack[start..].to_vec();
+        cycle.push(id.to_string());
+        return Err(ClawdError::DependencyCycle(cycle.join(" -> ")));
+    }
+
+    stack.push(id.to_string());
+    for dep in &nodes[id].1 {
+        visit(&dep.id, nodes, stack, done, order)?;
+    }
+    stack.pop();
+
+    done.insert(id.to_string());
+    order.push(id.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    type Graph = BTreeMap<String, (SkillDetailResponse, Vec<Dependency>)>;
+
+    fn graph(edges: &[(&str, &[&str])]) -> Graph {
+        edges
+            .iter()
+            .map(|(id, deps)| {
+                let detail = serde_json::from_value(json!({
+                    "id": id,
+                    "title": id,
+                    "description": "",
+                    "category": "Other",
+                    "download_count": 0,
+                    "rating": 0.0,
+                }))
+                .unwrap();
+                let deps = deps.iter().map(|d| Dependency::parse(d).unwrap()).collect();
+                (id.to_string(), (detail, deps))
+            })
+            .collect()
+    }
+
+    fn install_order(root: &str, nodes: &Graph) -> Result<Vec<String>, ClawdError> {
+        let mut order = Vec::new();
+        visit(
+            root,
+            nodes,
+            &mut Vec::new(),
+            &mut HashSet::new(),
+            &mut order,
+        )?;
+        Ok(order)
+    }
+
+    fn cycle_message(root: &str, nodes: &Graph) -> String {
+        match install_order(root, nodes) {
+            Err(e @ ClawdError::DependencyCycle(_)) => e.to_string(),
+            Err(e) => panic!("expected a cycle, got {}", e),
+            Ok(order) => panic!("expected a cycle, got {:?}", order),
+        }
+    }
+
+    #[test]
+    fn diamond_installs_shared_dependency_once_and_first() {
+        let nodes = graph(&[
+            ("t/app", &["t/left", "t/right"]),
+            ("t/left", &["t/base"]),
+            ("t/right", &["t/base@^1"]),
+            ("t/base", &[]),
+        ]);
+        assert_eq!(
+            install_order("t/app", &nodes).unwrap(),
+            ["t/base", "t/left", "t/right", "t/app"]
+        );
+    }
+
+    #[test]
+    fn self_loop_is_a_cycle() {
+        let nodes = graph(&[("t/a", &["t/a"])]);
+        assert_eq!(cycle_message("t/a", &nodes), "Dependency cycle: t/a -> t/a");
+    }
+
+    #[test]
+    fn two_skill_cycle_names_the_path() {
+        let nodes = graph(&[("t/a", &["t/b"]), ("t/b", &["t/a"])]);
+        assert_eq!(
+            cycle_message("t/a", &nodes),
+            "Dependency cycle: t/a -> t/b -> t/a"
+        );
+    }
+
+    #[test]
+    fn cycle_below_the_root_leaves_the_root_out() {
+        let nodes = graph(&[("t/app", &["t/a"]), ("t/a", &["t/b"]), ("t/b", &["t/a"])]);
+        assert_eq!(
+            cycle_message("t/app", &nodes),
+            "Dependency cycle: t/a -> t/b -> t/a"
+        );
+    }
+
+    #[test]
+    fn parses_dependency_specs() {
+        let dep = Dependency::parse("internal:t/style@^1.2").unwrap();
+        assert_eq!(dep.registry.as_deref(), Some("internal"));
+        assert_eq!(dep.id, "t/style");
+        assert!(dep.matches(Some("1.4.0")));
+        assert!(dep.matches(Some("v1.2.0")));
+        assert!(!dep.matches(Some("2.0.0")));
+        assert!(!dep.matches(None));
+        assert_eq!(dep.to_string(), "internal:t/style@^1.2");
+
+        assert!(Dependency::parse("t/plain").unwrap().matches(None));
+        assert!(Dependency::parse("no-slash").is_err());
+        assert!(Dependency::parse("t/x@not-a-range").is_err());
+    }
+}
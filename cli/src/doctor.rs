@@ -0,0 +1,361 @@
+use crate::api::clawd::SkillFilters;
+use crate::config::{Config, InstallScope, DEFAULT_REGISTRY};
+use crate::error::ClawdError;
+use crate::output::OutputFormat;
+use crate::registry::{Registries, Registry};
+use crate::upgrade::{self, CURRENT_VERSION};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// GitHub quota below which downloads of larger skills may fail midway.
+const LOW_RATE_LIMIT: u32 = 10;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Serialize)]
+struct Check {
+    name: String,
+    status: Status,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: Status::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: Status::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: Status::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct DoctorDocument {
+    version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    api_url: String,
+    checks: Vec<Check>,
+}
+
+pub async fn execute_doctor(api_url: Option<String>, output: OutputFormat) -> Result<()> {
+    let config = Config::new(api_url)?;
+    let current_exe = env::current_exe().context("Failed to get current executable path")?;
+
+    output.status(format_args!(
+        "🩺 clawd v{} ({} {})\n",
+        CURRENT_VERSION,
+        env::consts::OS,
+        env::consts::ARCH
+    ));
+
+    let registries = Registries::new(&config);
+    let mut checks = vec![check_user_skills(&config), check_project_skills(&config)];
+    for registry in registries.iter() {
+        checks.push(check_api(registry, registries.is_multiple()).await);
+    }
+    checks.extend([
+        check_github(&config).await,
+        check_binary_writable(&current_exe),
+        check_on_path(&current_exe),
+        check_upgrade_leftovers(&current_exe),
+    ]);
+
+    for check in &checks {
+        let icon = match check.status {
+            Status::Pass => "✅",
+            Status::Warn => "⚠️ ",
+            Status::Fail => "❌",
+        };
+        output.status(format_args!("{} {}: {}", icon, check.name, check.message));
+        if let Some(hint) = &check.hint {
+            output.status(format_args!("   → {}", hint));
+        }
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let failed = count(Status::Fail);
+    output.status(format_args!(
+        "\n{} checks: {} passed, {} warnings, {} failed",
+        checks.len(),
+        count(Status::Pass),
+        count(Status::Warn),
+        failed
+    ));
+
+    if output.is_structured() {
+        output.print(&DoctorDocument {
+            version: CURRENT_VERSION,
+            os: env::consts::OS,
+            arch: env::consts::ARCH,
+            api_url: config.api_url,
+            checks,
+        })?;
+    }
+
+    if failed > 0 {
+        return Err(ClawdError::DoctorFailed(failed).into());
+    }
+    Ok(())
+}
+
+fn check_user_skills(config: &Config) -> Check {
+    const NAME: &str = "User skills";
+    let dir = &config.install_base_path;
+
+    if !dir.exists() {
+        return Check::warn(
+            NAME,
+            format!("{} does not exist yet", dir.display()),
+            "It's created by the first `clawd add --scope user`",
+        );
+    }
+    if !dir.is_dir() {
+        return Check::fail(
+            NAME,
+            format!("{} is not a directory", dir.display()),
+            format!(
+                "Move it out of the way so clawd can create {}",
+                dir.display()
+            ),
+        );
+    }
+    if !is_writable(dir) {
+        return Check::fail(
+            NAME,
+            format!("{} is not writable", dir.display()),
+            format!(
+                "Fix its ownership or permissions, e.g. chmod u+w {}",
+                dir.display()
+            ),
+        );
+    }
+
+    Check::pass(NAME, format!("{} is writable", dir.display()))
+}
+
+fn check_project_skills(config: &Config) -> Check {
+    const NAME: &str = "Project skills";
+
+    match config.skills_dir(InstallScope::Project) {
+        Ok(dir) => Check::pass(NAME, format!("found {}", dir.display())),
+        Err(ClawdError::NoProjectRoot) => Check::warn(
+            NAME,
+            "no .claude directory in the current directory or its parents",
+            "Create .claude in your project root to install with --scope project",
+        ),
+        Err(e) => Check::fail(NAME, e.to_string(), "Check the current directory exists"),
+    }
+}
+
+/// `named` labels the check with the registry's name, for when several are
+/// configured.
+async fn check_api(registry: &Registry, named: bool) -> Check {
+    let name = if named {
+        format!("Registry {}", registry.name)
+    } else {
+        "Registry API".to_string()
+    };
+    let client = &registry.client;
+    let url = client.base_url();
+    let setting = if registry.name == DEFAULT_REGISTRY {
+        "CLAWD_API_URL".to_string()
+    } else {
+        format!("registries.{}.url", registry.name)
+    };
+
+    let total = match client.list_skills(1, 1, &SkillFilters::default()).await {
+        Ok(page) => page.total,
+        Err(ClawdError::NetworkError(e)) if !e.is_decode() => {
+            return Check::fail(
+                name,
+                format!("{} is unreachable: {}", url, e),
+                format!("Check your network connection and {}", setting),
+            );
+        }
+        Err(e) => {
+            return Check::fail(
+                name,
+                format!("{} doesn't speak the clawd API: {}", url, e),
+                format!("Check {} points at a clawd registry", setting),
+            );
+        }
+    };
+
+    let message = format!("{} ({} skills)", url, total);
+    match client.get_index(None).await {
+        Ok(_) => Check::pass(name, message),
+        Err(ClawdError::IndexUnsupported(_)) => {
+            Check::pass(name, format!("{}, without an index document", message))
+        }
+        Err(e) => Check::warn(
+            name,
+            format!("{}, but its index document can't be used: {}", message, e),
+            "Run `clawd upgrade`; the registry may use a newer index format",
+        ),
+    }
+}
+
+async fn check_github(config: &Config) -> Check {
+    const NAME: &str = "GitHub API";
+
+    let limit = match config.github_client().rate_limit().await {
+        Ok(limit) => limit,
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("api.github.com is unreachable: {}", e),
+                "Skills download from GitHub; check your network or proxy settings",
+            );
+        }
+    };
+
+    let message = format!("{}/{} requests left", limit.remaining, limit.limit);
+    if limit.remaining >= LOW_RATE_LIMIT {
+        return Check::pass(NAME, message);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let minutes = limit.reset.saturating_sub(now).div_ceil(60);
+    let hint = format!("The quota resets in {} minute(s)", minutes);
+    if limit.remaining == 0 {
+        Check::fail(NAME, message, hint)
+    } else {
+        Check::warn(NAME, message, hint)
+    }
+}
+
+fn check_binary_writable(current_exe: &Path) -> Check {
+    const NAME: &str = "Upgrade";
+
+    if let Some(hint) = upgrade::managed_install_hint(current_exe) {
+        return Check::pass(NAME, "managed by a package manager").with_hint(hint);
+    }
+
+    match current_exe.parent() {
+        Some(dir) if is_writable(dir) => {
+            Check::pass(NAME, format!("{} is writable", dir.display()))
+        }
+        Some(dir) => Check::warn(
+            NAME,
+            format!("{} is not writable", dir.display()),
+            "Run `clawd upgrade` with sudo, or reinstall clawd into a directory you own",
+        ),
+        None => Check::warn(
+            NAME,
+            "can't determine where clawd is installed",
+            "Reinstall clawd with the install script",
+        ),
+    }
+}
+
+fn check_on_path(current_exe: &Path) -> Check {
+    const NAME: &str = "PATH";
+
+    let Some(dir) = current_exe.parent() else {
+        return Check::warn(
+            NAME,
+            "can't determine where clawd is installed",
+            "Reinstall clawd with the install script",
+        );
+    };
+    let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+
+    let on_path = env::var_os("PATH").is_some_and(|paths| {
+        env::split_paths(&paths).any(|p| fs::canonicalize(&p).unwrap_or(p) == dir)
+    });
+    if on_path {
+        Check::pass(NAME, format!("{} is on PATH", dir.display()))
+    } else {
+        Check::warn(
+            NAME,
+            format!("{} is not on PATH", dir.display()),
+            format!(
+                "Add it to your shell profile: export PATH=\"{}:$PATH\"",
+                dir.display()
+            ),
+        )
+    }
+}
+
+fn check_upgrade_leftovers(current_exe: &Path) -> Check {
+    const NAME: &str = "Upgrade leftovers";
+
+    let staging = upgrade::staging_path(current_exe);
+    if staging.exists() {
+        return Check::warn(
+            NAME,
+            format!("{} was left by an interrupted upgrade", staging.display()),
+            format!("Delete it: rm {}", staging.display()),
+        );
+    }
+
+    let backup = upgrade::backup_path(current_exe);
+    if backup.exists() {
+        return Check::pass(
+            NAME,
+            format!(
+                "{} is kept for `clawd upgrade --rollback`",
+                backup.display()
+            ),
+        )
+        .with_hint(format!(
+            "Delete it once you're happy with this version: rm {}",
+            backup.display()
+        ));
+    }
+
+    Check::pass(NAME, "none")
+}
+
+/// Whether a file can be created in `dir`, which permission bits alone
+/// don't answer (ACLs, read-only mounts).
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".clawd-doctor-{}", std::process::id()));
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            fs::remove_file(&probe).ok();
+            true
+        }
+        Err(_) => false,
+    }
+}
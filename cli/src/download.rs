@@ -1,85 +1,530 @@
-use crate::api::clawd::ClawdClient;
+use crate::api::clawd::DownloadResponse;
 use crate::api::github::GitHubClient;
-use crate::config::{Config, InstallScope};
+use crate::config::{Config, InstallScope, DEFAULT_REGISTRY};
+use crate::deps::{self, PlanStep};
 use crate::error::ClawdError;
+use crate::output::OutputFormat;
 use crate::prompts::{prompt_overwrite, prompt_scope};
+use crate::receipt::InstallReceipt;
+use crate::registry::{self, Registries};
+use crate::remove;
+use crate::requirements;
+use crate::validate::check_installed_skill;
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum InstallStatus {
+    Installed,
+    AlreadyInstalled,
+    Cancelled,
+}
+
+#[derive(Serialize)]
+struct InstallDocument {
+    id: String,
+    status: InstallStatus,
+    scope: InstallScope,
+    path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<DownloadResponse>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+    /// Dependencies installed (or found installed) along with the skill
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<InstallDocument>,
+}
+
+/// What a target's install directory holds before installing.
+pub enum Existing {
+    Nothing,
+    /// The same skill, installed by clawd
+    Installed,
+    /// Another skill, per its receipt
+    Other(String),
+    /// Files clawd didn't install
+    Unmanaged,
+}
+
+/// A skill from the install plan and where it goes.
+pub struct Target {
+    pub step: PlanStep,
+    pub path: PathBuf,
+    /// Already installed at the planned version
+    pub up_to_date: bool,
+    pub existing: Existing,
+}
+
+impl Target {
+    /// Whether installing overwrites something the user should be asked
+    /// about. Dependencies clawd installed are updated in place without
+    /// asking.
+    pub fn needs_confirmation(&self) -> bool {
+        match self.existing {
+            Existing::Unmanaged => true,
+            Existing::Installed => self.step.required_by.is_none(),
+            Existing::Nothing | Existing::Other(_) => false,
+        }
+    }
+}
+
+/// How and where `clawd add` installs a skill.
+pub struct InstallOptions {
+    pub scope: Option<String>,
+    /// Overwrite without confirmation
+    pub force: bool,
+    /// Custom installation path (overrides scope)
+    pub path: Option<PathBuf>,
+    /// Directory name to install under instead of the skill name
+    pub alias: Option<String>,
+    /// Install even when system requirements aren't met
+    pub ignore_requirements: bool,
+}
 
 pub async fn execute_download(
     skill_id: String,
-    scope_arg: Option<String>,
-    force: bool,
-    custom_path: Option<PathBuf>,
+    options: InstallOptions,
     api_url: Option<String>,
+    output: OutputFormat,
 ) -> Result<()> {
-    let (author, name) = parse_skill_id(&skill_id)?;
+    let InstallOptions {
+        scope: scope_arg,
+        force,
+        path: custom_path,
+        alias,
+        ignore_requirements,
+    } = options;
+
+    let (registry_name, id) = registry::split_registry(&skill_id);
+    let registry_name = registry_name.map(str::to_string);
+    let skill_id = id.to_string();
+    parse_skill_id(&skill_id)?;
+    if let Some(alias) = &alias {
+        if !is_valid_identifier(alias) {
+            anyhow::bail!(
+                "Invalid directory name '{}'. Use lowercase letters, digits and hyphens",
+                alias
+            );
+        }
+    }
+
+    let config = Config::new(api_url)?;
+    let registries = Registries::new(&config);
 
     let scope = if custom_path.is_some() {
         InstallScope::User
     } else if let Some(scope_str) = scope_arg {
         InstallScope::from_str(&scope_str)?
+    } else if let Some(scope) = config.default_scope {
+        scope
     } else {
         prompt_scope()?
     };
 
-    let config = Config::new(api_url)?;
-    let install_path = config.resolve_install_path(&name, custom_path.clone(), scope)?;
+    output.status(format_args!("🔍 Resolving {}...", skill_id));
+    let targets = plan_install(
+        &config,
+        &registries,
+        registry_name.as_deref(),
+        &skill_id,
+        alias.as_deref(),
+        custom_path,
+        scope,
+    )
+    .await?;
 
-    println!("📦 Downloading skill: {}/{}", author, name);
-    println!("📂 Target: {:?} ({})", install_path, scope);
+    // Settle every collision and overwrite before installing anything, so
+    // one that's refused or declined leaves the system untouched.
+    for target in targets.iter().filter(|t| !t.up_to_date) {
+        match &target.existing {
+            Existing::Other(installed) if !force => {
+                return Err(ClawdError::InstallCollision(
+                    target.path.display().to_string(),
+                    installed.clone(),
+                )
+                .into());
+            }
+            Existing::Other(installed) => {
+                output.status(format_args!("⚠️  Replacing {} (--force)", installed));
+            }
+            _ if !target.needs_confirmation() => {}
+            _ if force => output.status("⚠️  Overwriting existing skill (--force)"),
+            _ if prompt_overwrite(&target.path)? => output.status("⚠️  Overwriting existing skill"),
+            _ => {
+                output.status("❌ Installation cancelled");
+                output.print(&InstallDocument {
+                    id: skill_id,
+                    status: InstallStatus::Cancelled,
+                    scope,
+                    path: target.path.clone(),
+                    registry: None,
+                    version: None,
+                    source: None,
+                    warnings: Vec::new(),
+                    dependencies: Vec::new(),
+                })?;
+                return Ok(());
+            }
+        }
+    }
+
+    if targets.len() > 1 {
+        output.status("📋 Install plan:");
+        for target in &targets {
+            let step = &target.step;
+            let mut notes = Vec::new();
+            if let Some(required_by) = &step.required_by {
+                notes.push(format!("required by {}", required_by));
+            }
+            if registries.is_multiple() {
+                notes.push(format!("from {}", step.registry));
+            }
+            if target.up_to_date {
+                notes.push("already installed".to_string());
+            }
+            output.status(format_args!(
+                "   {} {}{}",
+                step.id,
+                step.detail.version.as_deref().unwrap_or(""),
+                if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join(", "))
+                }
+            ));
+        }
+    }
+
+    check_requirements(
+        &targets,
+        ignore_requirements || config.ignore_requirements,
+        output,
+    )?;
 
-    let clawd = ClawdClient::new(config.api_url);
-    let download_info = clawd.get_download_info(&author, &name).await?;
+    let github = config.github_client();
+    let mut documents = Vec::new();
+    for target in &targets {
+        let step = &target.step;
+        let document = InstallDocument {
+            id: step.id.clone(),
+            status: InstallStatus::AlreadyInstalled,
+            scope,
+            path: target.path.clone(),
+            registry: Some(step.registry.clone()),
+            version: step.detail.version.clone(),
+            source: None,
+            warnings: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        if target.up_to_date {
+            documents.push(document);
+            continue;
+        }
 
-    println!("📍 Source: {}/{}", download_info.repo, download_info.path);
+        output.status(format_args!(
+            "📦 Downloading skill: {}",
+            registry::qualified_id(&step.registry, &step.id)
+        ));
+        output.status(format_args!("📂 Target: {:?} ({})", target.path, scope));
 
-    if install_path.exists() {
-        if force {
-            println!("⚠️  Overwriting existing skill (--force)");
-            fs::remove_dir_all(&install_path)?;
+        let source = fetch_source(&registries, step).await?;
+        output.status(format_args!("📍 Source: {}/{}", source.repo, source.path));
+
+        let spinner = if output.is_structured() {
+            ProgressBar::hidden()
         } else {
-            let should_overwrite = prompt_overwrite(&install_path)?;
-            if should_overwrite {
-                println!("⚠️  Overwriting existing skill");
-                fs::remove_dir_all(&install_path)?;
-            } else {
-                println!("❌ Installation cancelled");
-                return Ok(());
+            ProgressBar::new_spinner()
+        };
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.green} {msg}")
+                .unwrap(),
+        );
+        spinner.set_message("Downloading files...");
+
+        let warnings = match install_target(&github, target, &source, &spinner).await {
+            Ok(warnings) => warnings,
+            Err(e) => {
+                spinner.finish_and_clear();
+                return Err(e.into());
+            }
+        };
+
+        spinner.finish_with_message("✅ Download complete!");
+        for warning in &warnings {
+            output.status(format_args!("⚠️  {}", warning));
+        }
+        output.status(format_args!("📂 Installed to: {:?}", target.path));
+
+        documents.push(InstallDocument {
+            status: InstallStatus::Installed,
+            source: Some(source),
+            warnings,
+            ..document
+        });
+    }
+
+    // The requested skill is always last in the plan.
+    let mut document = documents.pop().expect("plan includes the requested skill");
+    document.dependencies = documents;
+    output.print(&document)?;
+
+    Ok(())
+}
+
+/// Resolves `skill_id` and its dependencies, checks them against the install
+/// policy and works out where each goes and what's there now. `alias` and
+/// `custom_path` apply to the requested skill only.
+pub async fn plan_install(
+    config: &Config,
+    registries: &Registries,
+    registry: Option<&str>,
+    skill_id: &str,
+    alias: Option<&str>,
+    custom_path: Option<PathBuf>,
+    scope: InstallScope,
+) -> Result<Vec<Target>, ClawdError> {
+    let plan = deps::resolve(registries, registry, skill_id).await?;
+
+    if config.verified_only {
+        if let Some(step) = plan.iter().find(|step| !step.detail.skill.is_verified) {
+            return Err(ClawdError::PolicyViolation(format!(
+                "{} is not verified and policy.verified_only is set",
+                step.id
+            )));
+        }
+    }
+
+    let skills_dir = config.skills_dir(scope)?;
+    let installed = InstallReceipt::installed_in(&skills_dir);
+
+    let mut targets = Vec::new();
+    for step in plan {
+        let (author, name) = parse_skill_id(&step.id)?;
+        let is_root = step.required_by.is_none();
+        // A dependency may already be installed under another name, e.g.
+        // with --as, which only its receipt tells.
+        let found = if is_root {
+            None
+        } else {
+            remove::find_installed(
+                config,
+                &skills_dir,
+                &installed,
+                Some(&step.registry),
+                &step.id,
+            )
+        };
+        let path = match found {
+            Some(path) => path,
+            None => {
+                let dir_name =
+                    config.install_dir_name(&author, &name, if is_root { alias } else { None });
+                let custom_path = if is_root { custom_path.clone() } else { None };
+                config.resolve_install_path(&dir_name, custom_path, scope)?
             }
+        };
+
+        let receipt = InstallReceipt::read(&path);
+        let up_to_date = !is_root
+            && receipt.as_ref().is_some_and(|r| {
+                r.id == step.id
+                    && r.version == step.detail.version
+                    && r.registry.as_deref().unwrap_or(DEFAULT_REGISTRY) == step.registry
+            });
+        let existing = match receipt {
+            Some(receipt) if receipt.id == step.id => Existing::Installed,
+            Some(receipt) => Existing::Other(receipt.id),
+            None if path.exists() => Existing::Unmanaged,
+            None => Existing::Nothing,
+        };
+
+        targets.push(Target {
+            step,
+            path,
+            up_to_date,
+            existing,
+        });
+    }
+    Ok(targets)
+}
+
+/// The system requirement checks of every skill about to be installed.
+pub fn requirement_checks(targets: &[Target]) -> Vec<(&Target, requirements::Check)> {
+    let mut checks = Vec::new();
+    for target in targets.iter().filter(|t| !t.up_to_date) {
+        if let Some(reqs) = &target.step.detail.requirements {
+            checks.extend(requirements::check(reqs).into_iter().map(|c| (target, c)));
         }
     }
+    checks
+}
 
-    fs::create_dir_all(&install_path)?;
+/// Checks the system requirements of every skill about to be installed and
+/// prints a checklist. Fails when any is unmet, unless `ignore` is set.
+fn check_requirements(targets: &[Target], ignore: bool, output: OutputFormat) -> Result<()> {
+    let checks = requirement_checks(targets);
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    output.status("🔧 Checking requirements:");
+    for (target, check) in &checks {
+        let skill = if targets.len() > 1 {
+            format!(" [{}]", target.step.id)
+        } else {
+            String::new()
+        };
+        match (&check.problem, &check.found) {
+            (None, Some(found)) => {
+                output.status(format_args!("   ✅ {} ({}){}", check.label, found, skill))
+            }
+            (None, None) => output.status(format_args!("   ✅ {}{}", check.label, skill)),
+            (Some(problem), _) => {
+                output.status(format_args!("   ❌ {}: {}{}", check.label, problem, skill))
+            }
+        }
+    }
+
+    let unmet = checks.iter().filter(|(_, c)| !c.is_met()).count();
+    if unmet > 0 {
+        if !ignore {
+            return Err(ClawdError::RequirementsNotMet(unmet).into());
+        }
+        output.status(format_args!(
+            "⚠️  Installing despite {} unmet requirement(s) (--ignore-requirements)",
+            unmet
+        ));
+    }
 
-    let github = GitHubClient::new();
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message("Downloading files...");
+    Ok(())
+}
+
+/// Where the registry says to download `step` from.
+pub async fn fetch_source(
+    registries: &Registries,
+    step: &PlanStep,
+) -> Result<DownloadResponse, ClawdError> {
+    let (author, name) = parse_skill_id(&step.id)?;
+    registries
+        .get(&step.registry)?
+        .client
+        .get_download_info(&author, &name)
+        .await
+}
+
+/// Installs `target` from `source`, recording where it came from in its
+/// receipt. Returns warnings about the installed skill.
+pub async fn install_target(
+    github: &GitHubClient,
+    target: &Target,
+    source: &DownloadResponse,
+    progress: &ProgressBar,
+) -> Result<Vec<String>, ClawdError> {
+    let step = &target.step;
+    let receipt = InstallReceipt {
+        version: step.detail.version.clone(),
+        registry: (step.registry != DEFAULT_REGISTRY).then(|| step.registry.clone()),
+        dependencies: step.dependencies.iter().map(|d| d.id.clone()).collect(),
+        ..InstallReceipt::new(&step.id, source)
+    };
+    install_skill(github, &receipt, source, &target.path, progress).await
+}
+
+/// Downloads a skill into a staging directory next to `install_path`, checks
+/// its SKILL.md and writes an install receipt before moving it into place, so
+/// a failed download or a broken skill leaves any existing install untouched.
+/// Returns warnings about the installed skill.
+async fn install_skill(
+    github: &GitHubClient,
+    receipt: &InstallReceipt,
+    download_info: &DownloadResponse,
+    install_path: &Path,
+    progress: &ProgressBar,
+) -> Result<Vec<String>, ClawdError> {
+    let dir_name = install_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staging = sibling_path(install_path, "partial");
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+
+    let staged = match download_skill_files(github, download_info, &staging, progress).await {
+        Ok(()) => check_installed_skill(&staging, &dir_name).and_then(|warnings| {
+            receipt.write(&staging)?;
+            Ok(warnings)
+        }),
+        Err(e) => Err(e),
+    };
+    let warnings = match staged {
+        Ok(warnings) => warnings,
+        Err(e) => {
+            fs::remove_dir_all(&staging).ok();
+            return Err(e);
+        }
+    };
+
+    if install_path.exists() {
+        let backup = sibling_path(install_path, "old");
+        if backup.exists() {
+            fs::remove_dir_all(&backup)?;
+        }
+        fs::rename(install_path, &backup)?;
+        if let Err(e) = fs::rename(&staging, install_path) {
+            fs::rename(&backup, install_path).ok();
+            fs::remove_dir_all(&staging).ok();
+            return Err(e.into());
+        }
+        // The new skill is in place; a leftover backup is cleared next time.
+        fs::remove_dir_all(&backup).ok();
+    } else if let Err(e) = fs::rename(&staging, install_path) {
+        fs::remove_dir_all(&staging).ok();
+        return Err(e.into());
+    }
+
+    Ok(warnings)
+}
+
+/// A hidden sibling of `path`, e.g. `.brainstorming.partial`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}", name, suffix))
+}
+
+/// Downloads a skill's files into `install_path`, creating it if needed.
+async fn download_skill_files(
+    github: &GitHubClient,
+    download_info: &DownloadResponse,
+    install_path: &Path,
+    progress: &ProgressBar,
+) -> Result<(), ClawdError> {
+    fs::create_dir_all(install_path)?;
 
     github
         .download_directory(
             &download_info.repo,
             &download_info.path,
             download_info.git_ref.as_deref(),
-            &install_path,
-            &spinner,
+            install_path,
+            progress,
         )
-        .await?;
-
-    spinner.finish_with_message("✅ Download complete!");
-    println!("📂 Installed to: {:?}", install_path);
-
-    Ok(())
+        .await
 }
 
-fn parse_skill_id(skill_id: &str) -> Result<(String, String), ClawdError> {
+pub fn parse_skill_id(skill_id: &str) -> Result<(String, String), ClawdError> {
     let parts: Vec<&str> = skill_id.split('/').collect();
     if parts.len() != 2 {
         return Err(ClawdError::InvalidSkillId(skill_id.to_string()));
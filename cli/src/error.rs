@@ -29,6 +29,88 @@ pub enum ClawdError {
     #[error("No project root found. Could not locate .claude directory in current or parent directories.")]
     NoProjectRoot,
 
+    #[error("Registry at {0} does not support search")]
+    SearchUnsupported(String),
+
+    #[error("Registry at {0} does not publish an index")]
+    IndexUnsupported(String),
+
+    #[error("No local skill index found. Run 'clawd search --local' while online to build it.")]
+    NoLocalIndex,
+
+    #[error(
+        "No skill registry found at or above {0}. Expected authors.json and a skills/ directory."
+    )]
+    NoRegistryRoot(String),
+
+    #[error("Validation failed with {0} problem(s)")]
+    ValidationFailed(usize),
+
+    #[error("Downloaded skill is invalid: {0}")]
+    InvalidSkill(String),
+
+    #[error("{0} already holds {1}. Install under another directory with --as <dir-name> or CLAWD_NAMING=author-name, or pass --force to replace it.")]
+    InstallCollision(String, String),
+
+    #[error("Invalid dependency '{0}': {1}")]
+    InvalidDependency(String, String),
+
+    #[error("Unsatisfied dependency: {0}")]
+    UnsatisfiedDependency(String),
+
+    #[error("Dependency cycle: {0}")]
+    DependencyCycle(String),
+
+    #[error(
+        "{0} requirement(s) not met. Install them, or pass --ignore-requirements to install anyway"
+    )]
+    RequirementsNotMet(usize),
+
+    #[error("{0} check(s) failed")]
+    DoctorFailed(usize),
+
+    #[error("Blocked by policy: {0}")]
+    PolicyViolation(String),
+
+    #[error("Skill not installed: {0}")]
+    SkillNotInstalled(String),
+
+    #[error("Unknown registry '{0}'. Declare it under [registries.{0}] in the clawd config")]
+    UnknownRegistry(String),
+
     #[error("Interactive prompt error: {0}")]
     PromptError(String),
 }
+
+impl ClawdError {
+    /// Stable identifier for the error variant, used in structured output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InvalidSkillId(_) => "invalid_skill_id",
+            Self::SkillNotFound(_) => "skill_not_found",
+            Self::RateLimitExceeded => "rate_limit_exceeded",
+            Self::NetworkError(_) => "network_error",
+            Self::FileSystemError(_) => "file_system_error",
+            Self::GitHubError(_) => "github_error",
+            Self::InvalidResponse(_) => "invalid_response",
+            Self::InvalidScope(_) => "invalid_scope",
+            Self::NoProjectRoot => "no_project_root",
+            Self::SearchUnsupported(_) => "search_unsupported",
+            Self::IndexUnsupported(_) => "index_unsupported",
+            Self::NoLocalIndex => "no_local_index",
+            Self::NoRegistryRoot(_) => "no_registry_root",
+            Self::ValidationFailed(_) => "validation_failed",
+            Self::InvalidSkill(_) => "invalid_skill",
+            Self::InstallCollision(..) => "install_collision",
+            Self::InvalidDependency(..) => "invalid_dependency",
+            Self::UnsatisfiedDependency(_) => "unsatisfied_dependency",
+            Self::DependencyCycle(_) => "dependency_cycle",
+            Self::RequirementsNotMet(_) => "requirements_not_met",
+            Self::DoctorFailed(_) => "doctor_failed",
+            Self::PolicyViolation(_) => "policy_violation",
+            Self::SkillNotInstalled(_) => "skill_not_installed",
+            Self::UnknownRegistry(_) => "unknown_registry",
+            Self::PromptError(_) => "prompt_error",
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::api::clawd::{ClawdClient, IndexEntry, IndexFetch, SkillFilters, SkillResponse};
+use crate::error::ClawdError;
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a cached index is used before it's revalidated. Revalidation is a
+/// conditional request, so this can be short.
+pub const INDEX_TTL: Duration = Duration::from_secs(60 * 60);
+
+// BM25 tuning parameters
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+// Field weights: a term in the ID or title is worth more than one in the
+// description. Applied by repeating field tokens in the document.
+const ID_WEIGHT: usize = 3;
+const TITLE_WEIGHT: usize = 3;
+const TAG_WEIGHT: usize = 2;
+const CATEGORY_WEIGHT: usize = 1;
+const DESCRIPTION_WEIGHT: usize = 1;
+
+/// A local copy of every skill in a registry, used for offline search.
+#[derive(Serialize, Deserialize)]
+pub struct SkillIndex {
+    pub api_url: String,
+    pub fetched_at: u64,
+    /// ETag of the index document this copy was built from
+    #[serde(default)]
+    pub etag: Option<String>,
+    pub skills: Vec<IndexEntry>,
+}
+
+impl SkillIndex {
+    /// Downloads the registry's index document, or returns `None` when the
+    /// copy tagged `etag` is still current. Registries that don't publish an
+    /// index are read page by page instead.
+    pub async fn fetch(
+        client: &ClawdClient,
+        etag: Option<&str>,
+    ) -> Result<Option<Self>, ClawdError> {
+        let (skills, etag) = match client.get_index(etag).await {
+            Ok(IndexFetch::NotModified) => return Ok(None),
+            Ok(IndexFetch::Updated { document, etag }) => (document.skills, etag),
+            Err(ClawdError::IndexUnsupported(_)) => {
+                let filters = SkillFilters::default();
+                let skills: Vec<SkillResponse> = client.list_all(&filters).try_collect().await?;
+                (skills.into_iter().map(IndexEntry::from).collect(), None)
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(Some(Self {
+            api_url: client.base_url().to_string(),
+            fetched_at: now(),
+            etag,
+            skills,
+        }))
+    }
+
+    /// Returns the cached index, refreshing it first when it's missing or
+    /// stale. With `offline` set the network is never touched. A stale copy is
+    /// still used when the refresh fails.
+    pub async fn load_or_fetch(
+        cache_dir: &Path,
+        client: &ClawdClient,
+        offline: bool,
+    ) -> Result<Self, ClawdError> {
+        // Reading a local registry is as cheap as reading the cache.
+        if client.is_local() {
+            let index = Self::fetch(client, None).await?;
+            return Ok(index.expect("an unconditional fetch returns the index"));
+        }
+
+        let cached = Self::load(cache_dir, client.base_url());
+
+        let cached = match cached {
+            Some(index) if offline || !index.is_stale() => return Ok(index),
+            None if offline => return Err(ClawdError::NoLocalIndex),
+            cached => cached,
+        };
+
+        let etag = cached.as_ref().and_then(|index| index.etag.as_deref());
+        let index = match (Self::fetch(client, etag).await, cached) {
+            (Ok(Some(index)), _) => index,
+            (Ok(None), Some(mut index)) => {
+                index.fetched_at = now();
+                index
+            }
+            (Ok(None), None) => {
+                return Err(ClawdError::InvalidResponse(
+                    "registry answered 304 Not Modified to an unconditional index request"
+                        .to_string(),
+                ))
+            }
+            (Err(e), Some(index)) => {
+                eprintln!(
+                    "Could not refresh the local index ({}), using cached copy.",
+                    e
+                );
+                return Ok(index);
+            }
+            (Err(e), None) => return Err(e),
+        };
+
+        index.save(cache_dir)?;
+        Ok(index)
+    }
+
+    pub fn load(cache_dir: &Path, api_url: &str) -> Option<Self> {
+        let contents = fs::read_to_string(index_path(cache_dir, api_url)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self, cache_dir: &Path) -> Result<(), ClawdError> {
+        let path = index_path(cache_dir, &self.api_url);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json =
+            serde_json::to_string(self).map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn is_stale(&self) -> bool {
+        now().saturating_sub(self.fetched_at) >= INDEX_TTL.as_secs()
+    }
+
+    /// Combines the indexes of several registries for searching, or `None`
+    /// when there are none. The result is as old as its oldest part.
+    pub fn merge(indexes: Vec<Self>) -> Option<Self> {
+        let mut indexes = indexes.into_iter();
+        let mut merged = indexes.next()?;
+        for index in indexes {
+            merged.api_url = format!("{}, {}", merged.api_url, index.api_url);
+            merged.fetched_at = merged.fetched_at.min(index.fetched_at);
+            merged.etag = None;
+            merged.skills.extend(index.skills);
+        }
+        Some(merged)
+    }
+
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.fetched_at))
+    }
+
+    /// Ranks skills against `terms` with BM25 and returns those that match,
+    /// best first.
+    pub fn search(&self, terms: &[String]) -> Vec<&SkillResponse> {
+        let skills: Vec<&SkillResponse> = self.skills.iter().map(|e| &e.skill).collect();
+        let documents: Vec<Vec<String>> = skills.iter().map(|s| document_tokens(s)).collect();
+        let query: Vec<String> = terms.iter().flat_map(|t| tokenize(t)).collect();
+
+        if documents.is_empty() || query.is_empty() {
+            return Vec::new();
+        }
+
+        let total_docs = documents.len() as f64;
+        let avg_len = documents.iter().map(Vec::len).sum::<usize>() as f64 / total_docs;
+
+        let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+        for term in &query {
+            let df = documents
+                .iter()
+                .filter(|doc| doc.iter().any(|token| token_matches(token, term)))
+                .count();
+            doc_freq.insert(term, df);
+        }
+
+        let mut scored: Vec<(f64, &SkillResponse)> = documents
+            .iter()
+            .zip(skills)
+            .filter_map(|(doc, skill)| {
+                let len = doc.len() as f64;
+                let score: f64 = query
+                    .iter()
+                    .map(|term| {
+                        let tf: f64 = doc.iter().map(|token| term_frequency(token, term)).sum();
+                        if tf == 0.0 {
+                            return 0.0;
+                        }
+                        let df = doc_freq[term.as_str()] as f64;
+                        let idf = (1.0 + (total_docs - df + 0.5) / (df + 0.5)).ln();
+                        idf * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * len / avg_len))
+                    })
+                    .sum();
+                (score > 0.0).then_some((score, skill))
+            })
+            .collect();
+
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| b.download_count.cmp(&a.download_count))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        scored.into_iter().map(|(_, skill)| skill).collect()
+    }
+}
+
+fn document_tokens(skill: &SkillResponse) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut add = |text: &str, weight: usize| {
+        let field = tokenize(text);
+        for _ in 0..weight {
+            tokens.extend(field.iter().cloned());
+        }
+    };
+
+    add(&skill.id, ID_WEIGHT);
+    add(&skill.title, TITLE_WEIGHT);
+    for tag in &skill.tags {
+        add(tag, TAG_WEIGHT);
+    }
+    add(&skill.category, CATEGORY_WEIGHT);
+    add(&skill.description, DESCRIPTION_WEIGHT);
+
+    tokens
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn token_matches(token: &str, term: &str) -> bool {
+    term_frequency(token, term) > 0.0
+}
+
+/// Exact token matches count fully; prefix matches (e.g. "brainstorm" for
+/// "brainstorming") count half so partially typed words still find results.
+fn term_frequency(token: &str, term: &str) -> f64 {
+    if token == term {
+        1.0
+    } else if term.len() >= 3 && token.starts_with(term) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+fn index_path(cache_dir: &Path, api_url: &str) -> PathBuf {
+    let key: String = api_url
+        .trim_end_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    cache_dir.join("index").join(format!("{}.json", key))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn index(skills: &[(&str, &str, &str)]) -> SkillIndex {
+        let skills = skills
+            .iter()
+            .map(|(id, title, description)| {
+                let skill: SkillResponse = serde_json::from_value(json!({
+                    "id": id,
+                    "title": title,
+                    "description": description,
+                    "category": "Other",
+                    "download_count": 0,
+                    "rating": 0.0,
+                }))
+                .unwrap();
+                IndexEntry::from(skill)
+            })
+            .collect();
+        SkillIndex {
+            api_url: "file:///registry".to_string(),
+            fetched_at: 0,
+            etag: None,
+            skills,
+        }
+    }
+
+    fn search(index: &SkillIndex, query: &str) -> Vec<String> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_string).collect();
+        index
+            .search(&terms)
+            .into_iter()
+            .map(|s| s.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn id_and_title_matches_outrank_description_matches() {
+        let index = index(&[
+            (
+                "t/notes",
+                "Meeting notes",
+                "Summarize meetings, even from a PDF",
+            ),
+            ("t/pdf-tools", "PDF Tools", "Fill and merge documents"),
+            ("t/charts", "Charts", "Plot data"),
+            ("t/forms", "PDF forms", "Fill in forms"),
+        ]);
+        let results = search(&index, "pdf");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.last().unwrap(), "t/notes");
+        assert!(!results.contains(&"t/charts".to_string()));
+    }
+
+    #[test]
+    fn skills_matching_every_term_rank_first() {
+        let index = index(&[
+            (
+                "t/branches",
+                "Git branch cleaner",
+                "Deletes merged branches",
+            ),
+            ("t/messages", "Messages", "Write a good commit message"),
+            (
+                "t/git-commit",
+                "Git commit helper",
+                "Splits changes into commits",
+            ),
+            ("t/docs", "Docs", "Writes documentation"),
+        ]);
+        let results = search(&index, "git commit");
+        assert_eq!(results[0], "t/git-commit");
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn prefixes_match_and_empty_queries_match_nothing() {
+        let index = index(&[("obra/brainstorming", "Brainstorming", "Refine ideas")]);
+        assert_eq!(search(&index, "BRAINSTORM"), ["obra/brainstorming"]);
+        assert!(search(&index, "").is_empty());
+        assert!(search(&index, "zz").is_empty());
+    }
+}
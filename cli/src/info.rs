@@ -0,0 +1,291 @@
+use crate::api::clawd::{DownloadResponse, SkillDetailResponse};
+use crate::api::github::TreeEntry;
+use crate::config::Config;
+use crate::download::parse_skill_id;
+use crate::error::ClawdError;
+use crate::list::format_number;
+use crate::output::OutputFormat;
+use crate::registry::{self, Registries, Registry};
+use crate::search::{format_age, load_indexes};
+use anyhow::Result;
+use serde::Serialize;
+use termimad::MadSkin;
+
+#[derive(Serialize)]
+struct InfoDocument<'a> {
+    #[serde(flatten)]
+    detail: &'a SkillDetailResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a DownloadResponse>,
+    files: &'a [TreeEntry],
+    skill_md: Option<String>,
+}
+
+pub async fn execute_info(
+    skill_id: String,
+    no_readme: bool,
+    offline: bool,
+    api_url: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let (registry_name, skill_id) = registry::split_registry(&skill_id);
+    let (author, name) = parse_skill_id(skill_id)?;
+
+    let config = Config::new(api_url)?;
+    let registries = Registries::new(&config);
+
+    if offline {
+        return info_offline(&config, &registries, registry_name, skill_id, output).await;
+    }
+
+    let (registry, detail) = registries.get_skill(registry_name, &author, &name).await?;
+    let download_info = registry.client.get_download_info(&author, &name).await?;
+
+    if !output.is_structured() {
+        print_details(&detail, Some(&download_info), registries.is_multiple());
+    }
+
+    let github = config.github_client();
+    let tree = match github
+        .list_tree(
+            &download_info.repo,
+            &download_info.path,
+            download_info.git_ref.as_deref(),
+        )
+        .await
+    {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!();
+            eprintln!("Could not fetch file tree: {}", e);
+            Vec::new()
+        }
+    };
+
+    let skill_md_url = tree
+        .iter()
+        .find(|entry| !entry.is_dir && entry.name == "SKILL.md")
+        .and_then(|entry| entry.download_url.as_deref());
+
+    let skill_md = match skill_md_url {
+        Some(url) if !no_readme => {
+            let contents = github.download_file(url).await?;
+            Some(String::from_utf8_lossy(&contents).into_owned())
+        }
+        _ => None,
+    };
+
+    if output.is_structured() {
+        output.print(&InfoDocument {
+            detail: &detail,
+            source: Some(&download_info),
+            files: &tree,
+            skill_md,
+        })?;
+        return Ok(());
+    }
+
+    if !tree.is_empty() {
+        println!();
+        println!("Files:");
+        print_tree(&tree, "");
+    }
+
+    if no_readme {
+        return Ok(());
+    }
+
+    let skin = MadSkin::default();
+
+    if let Some(readme) = &detail.readme {
+        println!();
+        println!("README");
+        println!("{}", "-".repeat(6));
+        skin.print_text(strip_frontmatter(readme));
+    }
+
+    if let Some(contents) = &skill_md {
+        println!();
+        println!("SKILL.md");
+        println!("{}", "-".repeat(8));
+        skin.print_text(strip_frontmatter(contents));
+    }
+
+    Ok(())
+}
+
+/// Shows a skill from the cached registry index. Its files and docs live in
+/// its GitHub repository, so they're left out.
+async fn info_offline(
+    config: &Config,
+    registries: &Registries,
+    registry_name: Option<&str>,
+    skill_id: &str,
+    output: OutputFormat,
+) -> Result<()> {
+    let selected: Vec<&Registry> = match registry_name {
+        Some(name) => vec![registries.get(name)?],
+        None => registries.iter().collect(),
+    };
+    let index = load_indexes(&config.cache_dir, &selected, false, true).await?;
+    let age = format_age(index.age());
+
+    // The merged index keeps registry priority order, so the first match is
+    // the one an online lookup would find.
+    let entry = index
+        .skills
+        .into_iter()
+        .find(|entry| entry.skill.id == skill_id)
+        .ok_or_else(|| ClawdError::SkillNotFound(skill_id.to_string()))?;
+
+    // Indexes built from list pages don't know where the skill lives.
+    let source = match (entry.repo, entry.path) {
+        (Some(repo), Some(path)) => Some(DownloadResponse {
+            repo,
+            path,
+            git_ref: entry.git_ref,
+        }),
+        _ => None,
+    };
+    let detail = SkillDetailResponse {
+        skill: entry.skill,
+        version: entry.version,
+        license: entry.license,
+        contributors: entry.contributors,
+        created_at: entry.created_at,
+        requirements: entry.requirements,
+        readme: None,
+    };
+
+    if output.is_structured() {
+        output.print(&InfoDocument {
+            detail: &detail,
+            source: source.as_ref(),
+            files: &[],
+            skill_md: None,
+        })?;
+        return Ok(());
+    }
+
+    print_details(&detail, source.as_ref(), registries.is_multiple());
+    println!();
+    println!(
+        "From the local index, updated {} ago. Run without --offline to see files and docs.",
+        age
+    );
+
+    Ok(())
+}
+
+fn print_details(
+    detail: &SkillDetailResponse,
+    download_info: Option<&DownloadResponse>,
+    show_registry: bool,
+) {
+    let skill = &detail.skill;
+
+    println!("{}", skill.title);
+    println!("{}", skill.id);
+    println!();
+    println!("{}", skill.description);
+    println!();
+
+    print_field("Version", detail.version.as_deref().unwrap_or("-"));
+    print_field("Category", &skill.category);
+    if !skill.tags.is_empty() {
+        print_field("Tags", &skill.tags.join(", "));
+    }
+    if let Some(author) = &skill.author {
+        let mut value = format!("{} (@{})", author.name, author.github);
+        if let Some(url) = &author.url {
+            value.push_str(&format!(" {}", url));
+        }
+        print_field("Author", &value);
+    }
+    if !detail.contributors.is_empty() {
+        print_field("Contributors", &detail.contributors.join(", "));
+    }
+    print_field("License", detail.license.as_deref().unwrap_or("-"));
+    print_field("Verified", if skill.is_verified { "yes" } else { "no" });
+    print_field("Downloads", &format_number(skill.download_count));
+    let rating = if skill.rating > 0.0 {
+        format!("{:.1}★", skill.rating)
+    } else {
+        "-".to_string()
+    };
+    print_field("Rating", &rating);
+    if let Some(created_at) = &detail.created_at {
+        print_field("Created", created_at);
+    }
+    if let Some(updated_at) = &skill.updated_at {
+        print_field("Updated", updated_at);
+    }
+
+    if let Some(requirements) = &detail.requirements {
+        if let Some(bash_version) = &requirements.bash_version {
+            print_field("Bash", bash_version);
+        }
+        if !requirements.dependencies.is_empty() {
+            print_field("Dependencies", &requirements.dependencies.join(", "));
+        }
+        if !requirements.commands.is_empty() {
+            print_field("Commands", &requirements.commands.join(", "));
+        }
+        if !requirements.python_packages.is_empty() {
+            print_field("Python", &requirements.python_packages.join(", "));
+        }
+        if !requirements.node_packages.is_empty() {
+            print_field("Node", &requirements.node_packages.join(", "));
+        }
+        for (key, value) in &requirements.other {
+            print_field(key, &value.to_string());
+        }
+    }
+
+    println!();
+    if show_registry {
+        print_field("Registry", skill.registry.as_deref().unwrap_or("-"));
+    }
+    if let Some(download_info) = download_info {
+        print_field("Repository", &download_info.repo);
+        print_field("Path", &download_info.path);
+        print_field(
+            "Ref",
+            download_info.git_ref.as_deref().unwrap_or("default branch"),
+        );
+    }
+}
+
+fn print_field(label: &str, value: &str) {
+    println!("{:<14} {}", format!("{}:", label), value);
+}
+
+fn print_tree(entries: &[TreeEntry], prefix: &str) {
+    for (i, entry) in entries.iter().enumerate() {
+        let last = i == entries.len() - 1;
+        let branch = if last { "└── " } else { "├── " };
+        let suffix = if entry.is_dir { "/" } else { "" };
+        println!("{}{}{}{}", prefix, branch, entry.name, suffix);
+
+        if entry.is_dir {
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            print_tree(&entry.children, &child_prefix);
+        }
+    }
+}
+
+/// Drops a leading `---` YAML frontmatter block so only the markdown body is
+/// rendered.
+pub fn strip_frontmatter(contents: &str) -> &str {
+    let Some(rest) = contents.strip_prefix("---") else {
+        return contents;
+    };
+
+    match rest.find("\n---") {
+        Some(end) => {
+            let body = &rest[end + 4..];
+            body.trim_start_matches(['\r', '\n'])
+        }
+        None => contents,
+    }
+}
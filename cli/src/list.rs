@@ -1,66 +1,316 @@
-use crate::api::clawd::ClawdClient;
+use crate::api::clawd::{ListResponse, SkillFilters, SkillResponse, MAX_PAGE_SIZE};
 use crate::config::Config;
 use crate::error::ClawdError;
+use crate::index::SkillIndex;
+use crate::output::OutputFormat;
+use crate::registry::{Registries, Registry};
+use crate::search::{format_age, load_indexes, paginate};
+use crate::table::{Column, Table};
+use futures_util::TryStreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct CatalogDocument {
+    skills: Vec<SkillResponse>,
+    total: usize,
+}
 
 pub async fn execute_list(
     page: u32,
     limit: u32,
+    all: bool,
+    filters: SkillFilters,
+    offline: bool,
     api_url: Option<String>,
+    output: OutputFormat,
 ) -> Result<(), ClawdError> {
-    let config = Config::new(api_url).map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
-    let client = ClawdClient::new(config.api_url);
+    let config =
+        Config::new(api_url).map_err(|e| ClawdError::InvalidResponse(format!("{:#}", e)))?;
+    let registries = Registries::new(&config);
 
-    let response = client.list_skills(page, limit).await?;
+    if offline {
+        let selected: Vec<&Registry> = registries.iter().collect();
+        let index =
+            load_indexes(&config.cache_dir, &selected, registries.is_multiple(), true).await?;
+        return list_index(&index, page, limit, all, &filters, &registries, output);
+    }
 
-    if response.skills.is_empty() {
+    if all {
+        return list_all(&registries, &filters, output).await;
+    }
+
+    // Every registry contributes its own page, so the page numbers stay
+    // meaningful for each of them.
+    let mut responses = Vec::new();
+    let mut last_error = None;
+    for registry in registries.iter() {
+        match registry.client.list_skills(page, limit, &filters).await {
+            Ok(mut response) => {
+                for skill in &mut response.skills {
+                    skill.registry = Some(registry.name.clone());
+                }
+                responses.push(response);
+            }
+            Err(e) => {
+                if registries.is_multiple() {
+                    eprintln!("⚠️  Skipping registry {}: {}", registry.name, e);
+                }
+                last_error = Some(e);
+            }
+        }
+    }
+    // One unreachable registry shouldn't hide the others.
+    if responses.is_empty() {
+        return Err(last_error.expect("at least one registry is configured"));
+    }
+    let response = merge_pages(responses);
+
+    // Older registries ignore the filter parameters, so apply them here too
+    let mut skills: Vec<SkillResponse> = response
+        .skills
+        .into_iter()
+        .filter(|s| filters.matches(s))
+        .collect();
+    filters.sort(&mut skills);
+
+    if output.is_structured() {
+        return output.print(&ListResponse { skills, ..response });
+    }
+
+    if skills.is_empty() {
         println!("No skills found.");
         return Ok(());
     }
 
-    // Print header
+    print_skills_table(&skills, &[], &registries, output == OutputFormat::Wide);
+
+    // Print pagination info
+    println!();
     println!(
-        "{:<30} {:<20} {:<15} {:>10} {:>8}",
-        "ID", "TITLE", "CATEGORY", "DOWNLOADS", "RATING"
+        "Page {} of {} ({} total skills)",
+        response.page, response.total_pages, response.total
     );
-    println!("{}", "-".repeat(87));
 
-    // Print skills
-    for skill in &response.skills {
-        let rating = if skill.rating > 0.0 {
-            format!("{:.1}★", skill.rating)
+    Ok(())
+}
+
+/// Combines the same page from several registries, in registry order.
+pub fn merge_pages(responses: Vec<ListResponse>) -> ListResponse {
+    let mut merged = ListResponse {
+        skills: Vec::new(),
+        total: 0,
+        page: 0,
+        limit: 0,
+        total_pages: 0,
+    };
+    for response in responses {
+        merged.skills.extend(response.skills);
+        merged.total += response.total;
+        merged.page = merged.page.max(response.page);
+        merged.limit = merged.limit.max(response.limit);
+        merged.total_pages = merged.total_pages.max(response.total_pages);
+    }
+    merged
+}
+
+/// Walks every page of every registry's catalog and prints it as one table.
+async fn list_all(
+    registries: &Registries,
+    filters: &SkillFilters,
+    output: OutputFormat,
+) -> Result<(), ClawdError> {
+    let progress = if output.is_structured() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new_spinner()
+    };
+    progress.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .unwrap(),
+    );
+    progress.set_message("Fetching skills...");
+
+    let mut skills = Vec::new();
+    let mut reached = 0;
+    let mut last_error = None;
+    for registry in registries.iter() {
+        let from = if registries.is_multiple() {
+            format!(" from {}", registry.name)
         } else {
-            "-".to_string()
+            String::new()
         };
+        let mut pages = Box::pin(registry.client.list_pages(MAX_PAGE_SIZE, filters));
+        loop {
+            let page = match pages.try_next().await {
+                Ok(Some(page)) => page,
+                Ok(None) => {
+                    reached += 1;
+                    break;
+                }
+                Err(e) => {
+                    if registries.is_multiple() {
+                        progress.suspend(|| {
+                            eprintln!("⚠️  Skipping registry {}: {}", registry.name, e)
+                        });
+                    }
+                    last_error = Some(e);
+                    break;
+                }
+            };
+            progress.set_message(format!(
+                "Fetching skills{}... page {} of {}",
+                from, page.page, page.total_pages
+            ));
+            skills.extend(
+                page.skills
+                    .into_iter()
+                    .filter(|s| filters.matches(s))
+                    .map(|s| SkillResponse {
+                        registry: Some(registry.name.clone()),
+                        ..s
+                    }),
+            );
+        }
+    }
+    progress.finish_and_clear();
+    if reached == 0 {
+        return Err(last_error.expect("at least one registry is configured"));
+    }
+    filters.sort(&mut skills);
 
-        println!(
-            "{:<30} {:<20} {:<15} {:>10} {:>8}",
-            truncate(&skill.id, 29),
-            truncate(&skill.title, 19),
-            truncate(&skill.category, 14),
-            format_number(skill.download_count),
-            rating
-        );
+    if output.is_structured() {
+        let total = skills.len();
+        return output.print(&CatalogDocument { skills, total });
     }
 
-    // Print pagination info
+    if skills.is_empty() {
+        println!("No skills found.");
+        return Ok(());
+    }
+
+    print_skills_table(&skills, &[], registries, output == OutputFormat::Wide);
+
+    println!();
+    println!("{} skills", skills.len());
+
+    Ok(())
+}
+
+/// Lists skills from the cached registry index without touching the network,
+/// either one page of it or, with `all`, all of it.
+fn list_index(
+    index: &SkillIndex,
+    page: u32,
+    limit: u32,
+    all: bool,
+    filters: &SkillFilters,
+    registries: &Registries,
+    output: OutputFormat,
+) -> Result<(), ClawdError> {
+    let mut skills: Vec<SkillResponse> = index
+        .skills
+        .iter()
+        .map(|entry| &entry.skill)
+        .filter(|s| filters.matches(s))
+        .cloned()
+        .collect();
+    filters.sort(&mut skills);
+    let age = format_age(index.age());
+
+    if all {
+        if output.is_structured() {
+            let total = skills.len();
+            return output.print(&CatalogDocument { skills, total });
+        }
+        if skills.is_empty() {
+            println!("No skills found.");
+            return Ok(());
+        }
+        print_skills_table(&skills, &[], registries, output == OutputFormat::Wide);
+        println!();
+        println!("{} skills (local index updated {} ago)", skills.len(), age);
+        return Ok(());
+    }
+
+    let response = paginate(skills, page, limit);
+
+    if output.is_structured() {
+        return output.print(&response);
+    }
+
+    if response.skills.is_empty() {
+        println!("No skills found.");
+        return Ok(());
+    }
+
+    print_skills_table(
+        &response.skills,
+        &[],
+        registries,
+        output == OutputFormat::Wide,
+    );
+
     println!();
     println!(
-        "Page {} of {} ({} total skills)",
-        response.page, response.total_pages, response.total
+        "Page {} of {} ({} total skills, local index updated {} ago)",
+        response.page, response.total_pages, response.total, age
     );
 
     Ok(())
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() > max {
-        format!("{}…", &s[..max - 1])
-    } else {
-        s.to_string()
+/// Prints skills as a table sized to the terminal. Occurrences of
+/// `highlight_terms` are highlighted when colors are enabled. A REGISTRY
+/// column is added when more than one registry is configured.
+pub fn print_skills_table(
+    skills: &[SkillResponse],
+    highlight_terms: &[String],
+    registries: &Registries,
+    wide: bool,
+) {
+    let show_registry = registries.is_multiple();
+    let mut columns = vec![
+        Column::new("ID").flexible(16).highlight(),
+        Column::new("TITLE").flexible(12).highlight(),
+        Column::new("CATEGORY").flexible(8),
+        Column::new("DOWNLOADS").right(),
+        Column::new("RATING").right(),
+        Column::new("DESCRIPTION").flexible(12).wrap().highlight(),
+    ];
+    if show_registry {
+        columns.insert(0, Column::new("REGISTRY"));
+    }
+    let mut table = Table::new(columns)
+        .highlight_terms(highlight_terms)
+        .wide(wide);
+
+    for skill in skills {
+        let rating = if skill.rating > 0.0 {
+            format!("{:.1}★", skill.rating)
+        } else {
+            "-".to_string()
+        };
+
+        let mut row = vec![
+            skill.id.clone(),
+            skill.title.clone(),
+            skill.category.clone(),
+            format_number(skill.download_count),
+            rating,
+            skill.description.clone(),
+        ];
+        if show_registry {
+            row.insert(0, skill.registry.clone().unwrap_or_default());
+        }
+        table.add_row(row);
     }
+
+    table.print();
 }
 
-fn format_number(n: i64) -> String {
+pub fn format_number(n: i64) -> String {
     if n >= 1_000_000 {
         format!("{:.1}M", n as f64 / 1_000_000.0)
     } else if n >= 1_000 {
@@ -1,13 +1,39 @@
-use clap::{Parser, Subcommand};
+use api::clawd::{SkillFilters, SortOrder};
+use category::Category;
+use clap::{Args, Parser, Subcommand};
+use download::InstallOptions;
+use error::ClawdError;
+use output::OutputFormat;
+use search::SearchMode;
+use settings::Layer;
 use std::path::PathBuf;
 
 mod api;
+mod browse;
+mod build;
+mod category;
 mod config;
+mod deps;
+mod doctor;
 mod download;
 mod error;
+mod index;
+mod info;
 mod list;
+mod new;
+mod output;
 mod prompts;
+mod publish;
+mod receipt;
+mod registry;
+mod remove;
+mod requirements;
+mod search;
+mod settings;
+mod table;
+mod update_check;
 mod upgrade;
+mod validate;
 
 #[derive(Parser)]
 #[command(name = "clawd")]
@@ -15,6 +41,49 @@ mod upgrade;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Output format [default: table, or `output` from clawd config]
+    #[arg(short, long, global = true, value_enum)]
+    output: Option<OutputFormat>,
+
+    /// Show full table cells without truncating or wrapping (same as --output wide)
+    #[arg(long, global = true, conflicts_with = "output")]
+    wide: bool,
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    /// Only show skills in this category
+    #[arg(long, value_enum, ignore_case = true)]
+    category: Option<Category>,
+
+    /// Only show skills with this tag (repeat to require several)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Only show skills by this author ID
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Only show skills verified by the Clawd team
+    #[arg(long)]
+    verified_only: bool,
+
+    /// Sort order
+    #[arg(long, value_enum)]
+    sort: Option<SortOrder>,
+}
+
+impl From<FilterArgs> for SkillFilters {
+    fn from(args: FilterArgs) -> Self {
+        Self {
+            category: args.category,
+            tags: args.tags,
+            author: args.author,
+            verified_only: args.verified_only,
+            sort: args.sort,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -29,12 +98,55 @@ enum Commands {
         #[arg(short, long, default_value_t = 20)]
         limit: u32,
 
+        /// List every skill, fetching all pages
+        #[arg(short, long, conflicts_with_all = ["page", "limit"])]
+        all: bool,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+
+        /// List the cached registry index without any network access
+        #[arg(long)]
+        offline: bool,
+
         /// Override API URL
         #[arg(long, env = "CLAWD_API_URL")]
         api_url: Option<String>,
     },
     /// Search for a skill
-    Search { query: String },
+    ///
+    /// Results are ranked over the whole catalog when every registry's index
+    /// is cached and fresh, or always with --local or --offline. Otherwise
+    /// each page the registries return is ranked on its own.
+    Search {
+        /// Search terms matched against ID, title, description and tags
+        query: String,
+
+        /// Page number (default: 1)
+        #[arg(short, long, default_value_t = 1)]
+        page: u32,
+
+        /// Items per page (default: 20, max: 100)
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+
+        #[arg(long, help = format!(
+            "Rank results locally over the cached registry index, refreshing it when it's over {} old",
+            search::format_age(index::INDEX_TTL)
+        ))]
+        local: bool,
+
+        /// Search the cached registry index without any network access
+        #[arg(long, conflicts_with = "local")]
+        offline: bool,
+
+        /// Override API URL
+        #[arg(long, env = "CLAWD_API_URL")]
+        api_url: Option<String>,
+    },
     /// Add a skill
     Add {
         /// Skill ID in format: author/skill-name
@@ -52,42 +164,343 @@ enum Commands {
         #[arg(short, long)]
         path: Option<PathBuf>,
 
+        /// Install under this directory name instead of the skill name
+        #[arg(long = "as", value_name = "DIR_NAME", conflicts_with = "path")]
+        alias: Option<String>,
+
+        /// Install even if the skill's system requirements aren't met
+        #[arg(long)]
+        ignore_requirements: bool,
+
+        /// Override API URL
+        #[arg(long, env = "CLAWD_API_URL")]
+        api_url: Option<String>,
+    },
+    /// Remove an installed skill, warning about skills that depend on it
+    Remove {
+        /// Skill ID in format: author/skill-name
+        skill_id: String,
+
+        /// Installation scope: "user" (~/.claude/skills) or "project" (./.claude/skills)
+        #[arg(short, long, value_name = "SCOPE")]
+        scope: Option<String>,
+
+        /// Remove without confirmation, even if other skills depend on it or the directory has no clawd receipt
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Browse, preview and install skills in an interactive terminal UI
+    Browse {
+        /// Installation scope to start with: "user" or "project" (toggle with 's')
+        #[arg(short, long, value_name = "SCOPE")]
+        scope: Option<String>,
+
+        /// Override API URL
+        #[arg(long, env = "CLAWD_API_URL")]
+        api_url: Option<String>,
+    },
+    /// Show details, files and documentation for a skill
+    Info {
+        /// Skill ID in format: author/skill-name
+        skill_id: String,
+
+        /// Don't render README.mdx and SKILL.md
+        #[arg(long)]
+        no_readme: bool,
+
+        /// Show the skill from the cached registry index without any network access
+        #[arg(long)]
+        offline: bool,
+
+        /// Override API URL
+        #[arg(long, env = "CLAWD_API_URL")]
+        api_url: Option<String>,
+    },
+    /// Scaffold a new skill entry in a registry checkout
+    New {
+        /// Skill ID in format: author/skill-name
+        skill_id: String,
+    },
+    /// Prepare a registry submission for a skill in a local git checkout
+    Publish {
+        /// Directory containing the skill's SKILL.md
+        skill_dir: PathBuf,
+
+        /// Registry checkout to prepare the branch in (default: current directory)
+        #[arg(long, value_name = "PATH")]
+        registry: Option<PathBuf>,
+
+        /// Registry author ID (default: the GitHub owner of the skill's repo)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Skill category (prompted for new entries when omitted)
+        #[arg(long, value_enum, ignore_case = true)]
+        category: Option<Category>,
+
+        /// Tag for the entry (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+    },
+    /// Check registry entries against the README.mdx and SKILL.md schemas
+    Validate {
+        /// Registry checkout, author directory or skill directory (default: current directory)
+        path: Option<PathBuf>,
+    },
+    /// Manage self-hosted registries
+    Registry {
+        #[command(subcommand)]
+        action: RegistryAction,
+    },
+    /// Show and change settings in the clawd config files
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Check the environment for common problems
+    Doctor {
+        /// Print the report as JSON, e.g. for bug reports (same as --output json)
+        #[arg(long)]
+        json: bool,
+
         /// Override API URL
         #[arg(long, env = "CLAWD_API_URL")]
         api_url: Option<String>,
     },
     /// Upgrade clawd to the latest version
     Upgrade {
-        /// Force reinstall even if already on latest version
+        /// Force reinstall even if already on latest version, replacing the
+        /// binary even if a package manager installed it
         #[arg(short, long)]
         force: bool,
+
+        /// Replace the binary even if a package manager (Homebrew, cargo,
+        /// Nix, ...) installed it, without forcing a reinstall of the same
+        /// version
+        #[arg(long)]
+        allow_managed: bool,
+
+        /// Restore the binary that was replaced by the last upgrade
+        #[arg(long, conflicts_with_all = ["force", "allow_managed"])]
+        rollback: bool,
     },
 }
 
+#[derive(Subcommand)]
+enum RegistryAction {
+    /// Build static registry files from a skills/<author>/<name>/README.mdx tree
+    Build {
+        /// The skills directory of a registry checkout
+        skills_dir: PathBuf,
+
+        /// Where to write the registry; its api/ directory is replaced
+        out_dir: PathBuf,
+
+        /// Author list (default: authors.json next to the skills directory)
+        #[arg(long, value_name = "PATH")]
+        authors: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective value of a setting
+    Get {
+        /// Setting name, e.g. api_url or policy.verified_only
+        key: String,
+    },
+    /// Set a value in the user config file (~/.config/clawd/config.toml)
+    Set {
+        /// Setting name, e.g. api_url or policy.verified_only
+        key: String,
+
+        value: String,
+
+        #[command(flatten)]
+        file: ConfigFileArgs,
+    },
+    /// List every setting with its effective value and where it comes from
+    List,
+    /// Open a config file in $VISUAL or $EDITOR
+    Edit {
+        #[command(flatten)]
+        file: ConfigFileArgs,
+    },
+}
+
+#[derive(Args)]
+struct ConfigFileArgs {
+    /// Use the project config (.claude/clawd.toml) instead of the user config
+    #[arg(long, conflicts_with = "system")]
+    project: bool,
+
+    /// Use the system-wide config (/etc/clawd/config.toml)
+    #[arg(long)]
+    system: bool,
+}
+
+impl From<ConfigFileArgs> for Layer {
+    fn from(args: ConfigFileArgs) -> Self {
+        if args.project {
+            Self::Project
+        } else if args.system {
+            Self::System
+        } else {
+            Self::User
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let output = match cli.command {
+        Commands::Doctor { json: true, .. } => OutputFormat::Json,
+        _ if cli.wide => OutputFormat::Wide,
+        _ => cli
+            .output
+            .or_else(settings::configured_output)
+            .unwrap_or_default(),
+    };
+
+    let update_check = match cli.command {
+        Commands::Upgrade { .. } => None,
+        _ if output.is_structured() => None,
+        _ => update_check::spawn(),
+    };
+
+    if let Err(e) = run(cli.command, output).await {
+        if output.is_structured() {
+            // Failed validations and doctor checks were already printed as
+            // the command's report.
+            if !matches!(
+                e.downcast_ref::<ClawdError>(),
+                Some(ClawdError::ValidationFailed(_) | ClawdError::DoctorFailed(_))
+            ) {
+                output.print_error(&e);
+            }
+            std::process::exit(1);
+        }
+        return Err(e);
+    }
+
+    if let Some(handle) = update_check {
+        update_check::notify(handle).await;
+    }
+
+    Ok(())
+}
 
-    match cli.command {
+async fn run(command: Commands, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
         Commands::List {
             page,
             limit,
+            all,
+            filters,
+            offline,
+            api_url,
+        } => {
+            list::execute_list(page, limit, all, filters.into(), offline, api_url, output).await?;
+        }
+        Commands::Search {
+            query,
+            page,
+            limit,
+            filters,
+            local,
+            offline,
             api_url,
         } => {
-            list::execute_list(page, limit, api_url).await?;
+            let mode = if offline {
+                SearchMode::Offline
+            } else if local {
+                SearchMode::Local
+            } else {
+                SearchMode::Remote
+            };
+            search::execute_search(query, page, limit, filters.into(), mode, api_url, output)
+                .await?;
         }
-        Commands::Search { query } => println!("Searching for: {}", query),
         Commands::Add {
             skill_id,
             scope,
             force,
             path,
+            alias,
+            ignore_requirements,
+            api_url,
+        } => {
+            let options = InstallOptions {
+                scope,
+                force,
+                path,
+                alias,
+                ignore_requirements,
+            };
+            download::execute_download(skill_id, options, api_url, output).await?;
+        }
+        Commands::Remove {
+            skill_id,
+            scope,
+            force,
+        } => {
+            remove::execute_remove(skill_id, scope, force, output)?;
+        }
+        Commands::Browse { scope, api_url } => {
+            browse::execute_browse(scope, api_url).await?;
+        }
+        Commands::Info {
+            skill_id,
+            no_readme,
+            offline,
             api_url,
         } => {
-            download::execute_download(skill_id, scope, force, path, api_url).await?;
+            info::execute_info(skill_id, no_readme, offline, api_url, output).await?;
+        }
+        Commands::New { skill_id } => {
+            new::execute_new(skill_id, output)?;
+        }
+        Commands::Publish {
+            skill_dir,
+            registry,
+            author,
+            category,
+            tags,
+        } => {
+            publish::execute_publish(skill_dir, registry, author, category, tags, output)?;
         }
-        Commands::Upgrade { force } => {
-            upgrade::execute_upgrade(force).await?;
+        Commands::Validate { path } => {
+            validate::execute_validate(path, output)?;
+        }
+        Commands::Registry { action } => match action {
+            RegistryAction::Build {
+                skills_dir,
+                out_dir,
+                authors,
+            } => build::execute_registry_build(skills_dir, out_dir, authors, output)?,
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Get { key } => settings::execute_config_get(key, output)?,
+            ConfigAction::Set { key, value, file } => {
+                settings::execute_config_set(key, value, file.into(), output)?
+            }
+            ConfigAction::List => settings::execute_config_list(output)?,
+            ConfigAction::Edit { file } => settings::execute_config_edit(file.into())?,
+        },
+        Commands::Doctor { api_url, .. } => {
+            doctor::execute_doctor(api_url, output).await?;
+        }
+        Commands::Upgrade {
+            force,
+            allow_managed,
+            rollback,
+        } => {
+            if rollback {
+                upgrade::execute_rollback(output)?;
+            } else {
+                upgrade::execute_upgrade(force, allow_managed, output).await?;
+            }
         }
     }
 
@@ -0,0 +1,232 @@
+use crate::category::Category;
+use crate::download::parse_skill_id;
+use crate::output::OutputFormat;
+use crate::prompts;
+use crate::validate::find_registry_root;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INITIAL_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+struct ReadmeFrontmatter<'a> {
+    id: &'a str,
+    title: &'a str,
+    description: &'a str,
+    version: &'a str,
+    category: Category,
+    author: &'a str,
+    repo: &'a str,
+    path: &'a str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+}
+
+#[derive(Serialize)]
+struct SkillFrontmatter<'a> {
+    name: &'a str,
+    description: &'a str,
+}
+
+#[derive(Serialize)]
+struct AuthorEntry {
+    name: String,
+    github: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    avatar: String,
+}
+
+#[derive(Serialize)]
+struct NewSkillDocument {
+    id: String,
+    path: PathBuf,
+    files: Vec<PathBuf>,
+    author_added: bool,
+}
+
+pub fn execute_new(skill_id: String, output: OutputFormat) -> Result<()> {
+    let (author, name) = parse_skill_id(&skill_id)?;
+
+    let cwd = std::env::current_dir()?;
+    let root = find_registry_root(&cwd)?;
+    let skill_dir = root.join("skills").join(&author).join(&name);
+    if skill_dir.exists() {
+        bail!(
+            "{} already exists",
+            display_path(&skill_dir, &cwd).display()
+        );
+    }
+
+    let authors_path = root.join("authors.json");
+    let mut authors: Map<String, Value> = serde_json::from_str(
+        &fs::read_to_string(&authors_path)
+            .with_context(|| format!("Cannot read {}", authors_path.display()))?,
+    )
+    .with_context(|| format!("Invalid JSON in {}", authors_path.display()))?;
+
+    let new_author = if authors.contains_key(&author) {
+        None
+    } else {
+        eprintln!("Author '{}' is not in authors.json yet.", author);
+        Some(prompt_author(&author)?)
+    };
+
+    let title = prompts::prompt_text("Title", Some(&default_title(&name)), required)?;
+    let description = prompts::prompt_text("Description", None, required)?;
+    let category = prompts::prompt_category()?;
+    let tags = prompts::prompt_tags()?;
+    let repo = prompts::prompt_text("GitHub repository (owner/repo)", None, |value| {
+        if is_valid_repo(value) {
+            Ok(())
+        } else {
+            Err("Expected owner/repo".to_string())
+        }
+    })?;
+    let path = prompts::prompt_text(
+        "Path to the skill within the repository",
+        Some(&format!("skills/{}", name)),
+        required,
+    )?;
+
+    let readme = render_markdown(
+        &ReadmeFrontmatter {
+            id: &skill_id,
+            title: &title,
+            description: &description,
+            version: INITIAL_VERSION,
+            category,
+            author: &author,
+            repo: &repo,
+            path: &path,
+            tags: &tags,
+        },
+        &format!("# {}\n\n{}\n", title, description),
+    )?;
+    let skill_md = render_markdown(
+        &SkillFrontmatter {
+            name: &name,
+            description: &description,
+        },
+        &format!(
+            "# {}\n\n## When to Use\n\nDescribe the situations this skill is for.\n\n\
+             ## Instructions\n\nStep-by-step guidance for carrying out the task.\n",
+            title
+        ),
+    )?;
+
+    let readme_path = skill_dir.join("README.mdx");
+    let skill_md_path = skill_dir.join("skill").join("SKILL.md");
+    fs::create_dir_all(skill_dir.join("skill"))?;
+    fs::write(&readme_path, readme)?;
+    fs::write(&skill_md_path, skill_md)?;
+
+    let author_added = new_author.is_some();
+    if let Some(entry) = new_author {
+        authors.insert(author.clone(), serde_json::to_value(entry)?);
+        authors.sort_keys();
+        let json = serde_json::to_string_pretty(&authors)?;
+        fs::write(&authors_path, format!("{}\n", json))?;
+    }
+
+    let mut files = vec![
+        display_path(&readme_path, &cwd),
+        display_path(&skill_md_path, &cwd),
+    ];
+    if author_added {
+        files.push(display_path(&authors_path, &cwd));
+    }
+
+    if output.is_structured() {
+        output.print(&NewSkillDocument {
+            id: skill_id,
+            path: display_path(&skill_dir, &cwd),
+            files,
+            author_added,
+        })?;
+        return Ok(());
+    }
+
+    println!();
+    for file in &files {
+        println!("✨ Wrote {}", file.display());
+    }
+    println!();
+    println!("Next: fill in skill/SKILL.md, then check your entry with:");
+    println!(
+        "  clawd validate {}",
+        display_path(&skill_dir, &cwd).display()
+    );
+
+    Ok(())
+}
+
+fn prompt_author(author: &str) -> Result<AuthorEntry> {
+    let name = prompts::prompt_text("Author display name", Some(author), required)?;
+    let github = prompts::prompt_text("GitHub username", Some(author), required)?;
+    let url = prompts::prompt_text("Website (optional)", None, |value| {
+        if value.is_empty() || value.starts_with("http://") || value.starts_with("https://") {
+            Ok(())
+        } else {
+            Err("Expected an http(s) URL".to_string())
+        }
+    })?;
+
+    Ok(AuthorEntry {
+        avatar: format!("https://github.com/{}.png", github),
+        url: (!url.is_empty()).then_some(url),
+        name,
+        github,
+    })
+}
+
+/// Renders a markdown file with `frontmatter` serialized as YAML.
+fn render_markdown<T: Serialize>(frontmatter: &T, body: &str) -> Result<String> {
+    let yaml = serde_yaml::to_string(frontmatter)?;
+    Ok(format!("---\n{}---\n\n{}", yaml, body))
+}
+
+fn required(value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        Err("This field is required".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Turns `writing-plans` into `Writing Plans`.
+pub fn default_title(name: &str) -> String {
+    name.split('-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Matches the `repo` pattern in `readme-schema.json`.
+pub fn is_valid_repo(repo: &str) -> bool {
+    let Some((owner, name)) = repo.split_once('/') else {
+        return false;
+    };
+    !owner.is_empty()
+        && !name.is_empty()
+        && owner
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+fn display_path(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd).unwrap_or(path).to_path_buf()
+}
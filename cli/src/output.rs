@@ -0,0 +1,83 @@
+use crate::error::ClawdError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable tables and status lines
+    #[default]
+    Table,
+    /// Like `table`, without truncating or wrapping cells
+    Wide,
+    /// A single JSON document on stdout
+    Json,
+    /// A single YAML document on stdout
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Whether the command should emit a machine-readable document instead of
+    /// human-readable text.
+    pub fn is_structured(&self) -> bool {
+        !matches!(self, Self::Table | Self::Wide)
+    }
+
+    /// Prints a human-readable status line. Suppressed for structured output
+    /// so stdout only ever holds the document.
+    pub fn status(&self, message: impl Display) {
+        if !self.is_structured() {
+            println!("{}", message);
+        }
+    }
+
+    pub fn print<T: Serialize>(&self, document: &T) -> Result<(), ClawdError> {
+        match self {
+            Self::Table | Self::Wide => {}
+            Self::Json => {
+                let json = serde_json::to_string_pretty(document)
+                    .map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
+                println!("{}", json);
+            }
+            Self::Yaml => {
+                let yaml = serde_yaml::to_string(document)
+                    .map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
+                print!("{}", yaml);
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints a failed command's error as a structured document.
+    pub fn print_error(&self, error: &anyhow::Error) {
+        let kind = error
+            .downcast_ref::<ClawdError>()
+            .map(ClawdError::kind)
+            .unwrap_or("error");
+
+        let document = ErrorDocument {
+            error: ErrorBody {
+                kind,
+                message: error.to_string(),
+                causes: error.chain().skip(1).map(|c| c.to_string()).collect(),
+            },
+        };
+
+        if self.print(&document).is_err() {
+            eprintln!("Error: {:#}", error);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorDocument {
+    error: ErrorBody,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    kind: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    causes: Vec<String>,
+}
@@ -1,6 +1,7 @@
+use crate::category::Category;
 use crate::config::InstallScope;
 use crate::error::ClawdError;
-use dialoguer::{Confirm, Select};
+use dialoguer::{Confirm, Input, Select};
 use std::path::Path;
 
 pub fn prompt_scope() -> Result<InstallScope, ClawdError> {
@@ -30,3 +31,53 @@ pub fn prompt_overwrite(path: &Path) -> Result<bool, ClawdError> {
         .interact()
         .map_err(|e| ClawdError::PromptError(e.to_string()))
 }
+
+pub fn prompt_confirm(prompt: &str) -> Result<bool, ClawdError> {
+    Confirm::new()
+        .with_prompt(prompt)
+        .default(false)
+        .interact()
+        .map_err(|e| ClawdError::PromptError(e.to_string()))
+}
+
+/// Asks for a line of text. `validate` returns an error message for input
+/// that should be rejected.
+pub fn prompt_text(
+    prompt: &str,
+    default: Option<&str>,
+    validate: impl Fn(&str) -> Result<(), String>,
+) -> Result<String, ClawdError> {
+    let mut input = Input::<String>::new()
+        .with_prompt(prompt)
+        .allow_empty(default.is_none())
+        .validate_with(|value: &String| validate(value.trim()));
+    if let Some(default) = default {
+        input = input.default(default.to_string());
+    }
+
+    input
+        .interact_text()
+        .map(|value| value.trim().to_string())
+        .map_err(|e| ClawdError::PromptError(e.to_string()))
+}
+
+pub fn prompt_category() -> Result<Category, ClawdError> {
+    let selection = Select::new()
+        .with_prompt("Select a category")
+        .items(Category::ALL.map(|c| c.as_str()))
+        .default(0)
+        .interact()
+        .map_err(|e| ClawdError::PromptError(e.to_string()))?;
+
+    Ok(Category::ALL[selection])
+}
+
+/// Asks for a comma-separated list of tags.
+pub fn prompt_tags() -> Result<Vec<String>, ClawdError> {
+    let tags = prompt_text("Tags (comma-separated, optional)", None, |_| Ok(()))?;
+    Ok(tags
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect())
+}
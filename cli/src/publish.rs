@@ -0,0 +1,320 @@
+use crate::category::Category;
+use crate::download::parse_skill_id;
+use crate::error::ClawdError;
+use crate::info::strip_frontmatter;
+use crate::new::{default_title, is_valid_repo, INITIAL_VERSION};
+use crate::output::OutputFormat;
+use crate::prompts;
+use crate::validate::{self, find_registry_root, Frontmatter};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Serialize)]
+struct PublishDocument {
+    id: String,
+    branch: String,
+    registry: PathBuf,
+    readme: PathBuf,
+    repo: String,
+    path: String,
+    #[serde(rename = "ref")]
+    git_ref: String,
+    updated: bool,
+}
+
+/// Where a skill lives upstream, derived from its local git checkout.
+struct SkillSource {
+    repo: String,
+    path: String,
+    git_ref: String,
+}
+
+pub fn execute_publish(
+    skill_dir: PathBuf,
+    registry: Option<PathBuf>,
+    author: Option<String>,
+    category: Option<Category>,
+    tags: Vec<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let skill_dir = skill_dir
+        .canonicalize()
+        .with_context(|| format!("Cannot access {}", skill_dir.display()))?;
+    let skill_md_path = skill_dir.join("SKILL.md");
+    let contents = fs::read_to_string(&skill_md_path)
+        .with_context(|| format!("Cannot read {}", skill_md_path.display()))?;
+    let skill_md = Frontmatter::parse(&contents).map_err(|(line, message)| {
+        anyhow::anyhow!("{}:{}: {}", skill_md_path.display(), line, message)
+    })?;
+
+    let skill_md = skill_md.data.as_object().cloned().unwrap_or_default();
+    let name = frontmatter_str(&skill_md, "name")
+        .with_context(|| format!("{} has no name", skill_md_path.display()))?;
+    let description = frontmatter_str(&skill_md, "description")
+        .with_context(|| format!("{} has no description", skill_md_path.display()))?;
+
+    let source = skill_source(&skill_dir)?;
+
+    let registry_dir = registry.unwrap_or_else(|| PathBuf::from("."));
+    let registry_dir = registry_dir
+        .canonicalize()
+        .with_context(|| format!("Cannot access {}", registry_dir.display()))?;
+    let root = find_registry_root(&registry_dir)?;
+
+    let authors: Map<String, Value> = serde_json::from_str(
+        &fs::read_to_string(root.join("authors.json")).context("Cannot read authors.json")?,
+    )
+    .context("Invalid JSON in authors.json")?;
+
+    // Authors are keyed by registry ID, which needn't match their GitHub
+    // login, so look the repository owner up by the `github` field.
+    let owner = source.repo.split('/').next().unwrap_or_default();
+    let author = author.unwrap_or_else(|| {
+        authors
+            .iter()
+            .find(|(_, entry)| {
+                entry
+                    .get("github")
+                    .and_then(Value::as_str)
+                    .is_some_and(|github| github.eq_ignore_ascii_case(owner))
+            })
+            .map(|(id, _)| id.clone())
+            .unwrap_or_else(|| owner.to_lowercase())
+    });
+    let skill_id = format!("{}/{}", author, name);
+    parse_skill_id(&skill_id)?;
+
+    if !authors.contains_key(&author) {
+        bail!(
+            "Author '{}' is not in authors.json. Add it first (clawd new does this), or pass --author.",
+            author
+        );
+    }
+
+    if !git(&root, &["status", "--porcelain"])?.is_empty() {
+        bail!(
+            "The registry checkout at {} has uncommitted changes",
+            root.display()
+        );
+    }
+
+    let entry_dir = root.join("skills").join(&author).join(name);
+    let readme_path = entry_dir.join("README.mdx");
+    let existing = fs::read_to_string(&readme_path).ok();
+    let updated = existing.is_some();
+
+    // Start from the current entry when updating, so curated fields such as
+    // tags, version and contributors survive.
+    let (mut frontmatter, body) = match &existing {
+        Some(contents) => {
+            let parsed = Frontmatter::parse(contents).map_err(|(line, message)| {
+                anyhow::anyhow!("{}:{}: {}", readme_path.display(), line, message)
+            })?;
+            let map = match parsed.data {
+                Value::Object(map) => map,
+                _ => Map::new(),
+            };
+            (map, Some(strip_frontmatter(contents).to_string()))
+        }
+        None => (Map::new(), None),
+    };
+
+    let title = frontmatter_str(&frontmatter, "title")
+        .map(str::to_string)
+        .or_else(|| skill_title(strip_frontmatter(&contents)))
+        .unwrap_or_else(|| default_title(name));
+    let body = body.unwrap_or_else(|| format!("# {}\n\n{}\n", title, description));
+    let version = frontmatter_str(&frontmatter, "version")
+        .unwrap_or(INITIAL_VERSION)
+        .to_string();
+    let category = match category {
+        Some(category) => category,
+        None => match frontmatter_str(&frontmatter, "category").and_then(Category::from_str) {
+            Some(category) => category,
+            None => prompts::prompt_category()?,
+        },
+    };
+
+    set(&mut frontmatter, "id", &skill_id);
+    set(&mut frontmatter, "title", &title);
+    set(&mut frontmatter, "description", description);
+    set(&mut frontmatter, "version", &version);
+    set(&mut frontmatter, "category", category.as_str());
+    set(&mut frontmatter, "author", &author);
+    set(&mut frontmatter, "repo", &source.repo);
+    set(&mut frontmatter, "path", &source.path);
+    set(&mut frontmatter, "ref", &source.git_ref);
+    if !tags.is_empty() {
+        frontmatter.insert("tags".to_string(), Value::from(tags));
+    }
+
+    let branch = format!(
+        "{}-{}-{}",
+        if updated { "update" } else { "add" },
+        author,
+        name
+    );
+    // -B so publishing the skill again reuses the branch from last time.
+    git(&root, &["checkout", "-B", &branch])?;
+
+    fs::create_dir_all(&entry_dir)?;
+    let yaml = serde_yaml::to_string(&frontmatter)?;
+    fs::write(&readme_path, format!("---\n{}---\n\n{}", yaml, body))?;
+
+    let (_, problems) = validate::check_path(&root, &entry_dir)?;
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        eprintln!();
+        eprintln!(
+            "Fix the entry on branch '{}' and run clawd validate before committing.",
+            branch
+        );
+        return Err(ClawdError::ValidationFailed(problems.len()).into());
+    }
+
+    let relative_readme = readme_path.strip_prefix(&root).unwrap_or(&readme_path);
+    git(&root, &["add", "--", &relative_readme.to_string_lossy()])?;
+
+    if output.is_structured() {
+        output.print(&PublishDocument {
+            id: skill_id,
+            branch,
+            registry: root,
+            readme: relative_readme.to_path_buf(),
+            repo: source.repo,
+            path: source.path,
+            git_ref: source.git_ref,
+            updated,
+        })?;
+        return Ok(());
+    }
+
+    println!(
+        "✅ {} {} on branch '{}'",
+        if updated { "Updated" } else { "Added" },
+        skill_id,
+        branch
+    );
+    println!("📂 {}", readme_path.display());
+    println!(
+        "🔗 {}@{} ({})",
+        source.repo,
+        short_ref(&source.git_ref),
+        source.path
+    );
+    println!();
+    println!("The entry is staged. To submit it:");
+    println!("  cd {}", root.display());
+    println!(
+        "  git commit -m \"{} {}\"",
+        if updated { "Update" } else { "Add" },
+        skill_id
+    );
+    println!("  git push -u origin {}", branch);
+    println!("Then open a pull request against the registry.");
+
+    Ok(())
+}
+
+/// Reads the GitHub repository, the skill's path in it and the commit it's
+/// checked out at.
+fn skill_source(skill_dir: &Path) -> Result<SkillSource> {
+    let toplevel = PathBuf::from(git(skill_dir, &["rev-parse", "--show-toplevel"])?)
+        .canonicalize()
+        .context("Cannot resolve the git checkout of the skill")?;
+
+    let remote = git(skill_dir, &["remote", "get-url", "origin"])?;
+    let repo = parse_github_remote(&remote)
+        .with_context(|| format!("Remote 'origin' ({}) is not a GitHub repository", remote))?;
+
+    let path = skill_dir
+        .strip_prefix(&toplevel)
+        .unwrap_or(Path::new(""))
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let git_ref = git(skill_dir, &["rev-parse", "HEAD"])?;
+
+    if !git(skill_dir, &["status", "--porcelain", "--", "."])?.is_empty() {
+        eprintln!(
+            "⚠️  {} has uncommitted changes that won't be part of the published ref",
+            skill_dir.display()
+        );
+    }
+    if git(skill_dir, &["branch", "-r", "--contains", "HEAD"])?.is_empty() {
+        eprintln!(
+            "⚠️  {} is not on any remote branch yet. Push it before opening the PR.",
+            short_ref(&git_ref)
+        );
+    }
+
+    Ok(SkillSource {
+        repo,
+        path,
+        git_ref,
+    })
+}
+
+/// Extracts `owner/repo` from an HTTPS or SSH GitHub remote URL.
+fn parse_github_remote(url: &str) -> Option<String> {
+    let rest = [
+        "https://github.com/",
+        "http://github.com/",
+        "ssh://git@github.com/",
+        "git@github.com:",
+    ]
+    .iter()
+    .find_map(|prefix| url.strip_prefix(prefix))?;
+    let repo = rest.trim_end_matches('/').trim_end_matches(".git");
+    is_valid_repo(repo).then(|| repo.to_string())
+}
+
+/// Runs git in `dir` and returns its trimmed stdout.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The first `# ` heading of a markdown body.
+fn skill_title(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+fn frontmatter_str<'a>(data: &'a Map<String, Value>, key: &str) -> Option<&'a str> {
+    data.get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+fn set(frontmatter: &mut Map<String, Value>, key: &str, value: &str) {
+    frontmatter.insert(key.to_string(), Value::from(value));
+}
+
+fn short_ref(git_ref: &str) -> &str {
+    git_ref.get(..7).unwrap_or(git_ref)
+}
@@ -0,0 +1,75 @@
+use crate::api::clawd::DownloadResponse;
+use crate::error::ClawdError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Written into every installed skill directory.
+pub const RECEIPT_FILE: &str = ".clawd.json";
+
+/// Records which registry skill an install directory holds, since the
+/// directory name alone doesn't say (see `--as` and `CLAWD_NAMING`).
+#[derive(Serialize, Deserialize)]
+pub struct InstallReceipt {
+    pub id: String,
+    pub repo: String,
+    pub path: String,
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Configured registry the skill came from; `None` for the public one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// IDs of the skills this one was installed to depend on
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    pub installed_at: u64,
+}
+
+impl InstallReceipt {
+    pub fn new(id: &str, source: &DownloadResponse) -> Self {
+        Self {
+            id: id.to_string(),
+            repo: source.repo.clone(),
+            path: source.path.clone(),
+            git_ref: source.git_ref.clone(),
+            version: None,
+            registry: None,
+            dependencies: Vec::new(),
+            installed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Reads the receipt in `dir`, if it was installed by clawd.
+    pub fn read(dir: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(dir.join(RECEIPT_FILE)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Every clawd-installed skill directly inside `skills_dir`.
+    pub fn installed_in(skills_dir: &Path) -> Vec<(PathBuf, Self)> {
+        let Ok(entries) = fs::read_dir(skills_dir) else {
+            return Vec::new();
+        };
+
+        let mut installed: Vec<(PathBuf, Self)> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter_map(|path| Self::read(&path).map(|receipt| (path, receipt)))
+            .collect();
+        installed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        installed
+    }
+
+    pub fn write(&self, dir: &Path) -> Result<(), ClawdError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
+        fs::write(dir.join(RECEIPT_FILE), json)?;
+        Ok(())
+    }
+}
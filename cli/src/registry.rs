@@ -0,0 +1,92 @@
+use crate::api::clawd::{ClawdClient, SkillDetailResponse};
+use crate::config::{Config, DEFAULT_REGISTRY};
+use crate::error::ClawdError;
+
+/// A configured registry and a client for it.
+pub struct Registry {
+    pub name: String,
+    pub client: ClawdClient,
+}
+
+/// Every configured registry, in lookup order (see `Config::registries`).
+pub struct Registries {
+    registries: Vec<Registry>,
+}
+
+impl Registries {
+    pub fn new(config: &Config) -> Self {
+        let registries = config
+            .registries
+            .iter()
+            .map(|registry| Registry {
+                name: registry.name.clone(),
+                client: ClawdClient::new(registry.url.clone())
+                    .with_token(registry.token.as_deref()),
+            })
+            .collect();
+        Self { registries }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Registry> {
+        self.registries.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Registry, ClawdError> {
+        self.registries
+            .iter()
+            .find(|registry| registry.name == name)
+            .ok_or_else(|| ClawdError::UnknownRegistry(name.to_string()))
+    }
+
+    /// Whether registries besides the public one are configured, which is
+    /// when output starts naming them.
+    pub fn is_multiple(&self) -> bool {
+        self.registries.len() > 1
+    }
+
+    /// Looks a skill up in `registry`, or in each registry in priority order
+    /// when it's `None`. Only "not found" moves on to the next registry: if a
+    /// registry can't be asked, a lower-priority one must not stand in for it.
+    pub async fn get_skill(
+        &self,
+        registry: Option<&str>,
+        author: &str,
+        name: &str,
+    ) -> Result<(&Registry, SkillDetailResponse), ClawdError> {
+        if let Some(registry) = registry {
+            let registry = self.get(registry)?;
+            let mut detail = registry.client.get_skill(author, name).await?;
+            detail.skill.registry = Some(registry.name.clone());
+            return Ok((registry, detail));
+        }
+
+        for registry in &self.registries {
+            match registry.client.get_skill(author, name).await {
+                Ok(mut detail) => {
+                    detail.skill.registry = Some(registry.name.clone());
+                    return Ok((registry, detail));
+                }
+                Err(ClawdError::SkillNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(ClawdError::SkillNotFound(format!("{}/{}", author, name)))
+    }
+}
+
+/// Splits `internal:team/skill` into the registry name and the skill ID.
+pub fn split_registry(id: &str) -> (Option<&str>, &str) {
+    match id.split_once(':') {
+        Some((registry, id)) => (Some(registry), id),
+        None => (None, id),
+    }
+}
+
+/// `registry:id` for skills outside the public registry, `id` otherwise.
+pub fn qualified_id(registry: &str, id: &str) -> String {
+    if registry == DEFAULT_REGISTRY {
+        id.to_string()
+    } else {
+        format!("{}:{}", registry, id)
+    }
+}
@@ -0,0 +1,144 @@
+use crate::config::{Config, InstallScope, DEFAULT_REGISTRY};
+use crate::download::parse_skill_id;
+use crate::error::ClawdError;
+use crate::output::OutputFormat;
+use crate::prompts::{prompt_confirm, prompt_scope};
+use crate::receipt::InstallReceipt;
+use crate::registry;
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RemoveStatus {
+    Removed,
+    Cancelled,
+}
+
+#[derive(Serialize)]
+struct RemoveDocument {
+    id: String,
+    status: RemoveStatus,
+    scope: InstallScope,
+    path: PathBuf,
+    /// Whether the directory has no install receipt
+    unmanaged: bool,
+    /// Installed skills that depend on the removed one
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependents: Vec<String>,
+}
+
+pub fn execute_remove(
+    skill_id: String,
+    scope_arg: Option<String>,
+    force: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let (registry_name, id) = registry::split_registry(&skill_id);
+    let registry_name = registry_name.map(str::to_string);
+    let skill_id = id.to_string();
+    parse_skill_id(&skill_id)?;
+
+    let config = Config::new(None)?;
+    let scope = if let Some(scope_str) = scope_arg {
+        InstallScope::from_str(&scope_str)?
+    } else if let Some(scope) = config.default_scope {
+        scope
+    } else {
+        prompt_scope()?
+    };
+
+    let skills_dir = config.skills_dir(scope)?;
+    let installed = InstallReceipt::installed_in(&skills_dir);
+    let path = find_installed(
+        &config,
+        &skills_dir,
+        &installed,
+        registry_name.as_deref(),
+        &skill_id,
+    )
+    .ok_or_else(|| ClawdError::SkillNotInstalled(skill_id.clone()))?;
+    let dependents = dependents(&installed, &skill_id);
+    // Without a receipt the directory only shares the skill's name; it may
+    // hold something the user wrote by hand.
+    let unmanaged = InstallReceipt::read(&path).is_none();
+
+    if unmanaged {
+        output.status(format_args!(
+            "⚠️  {} is unmanaged: it has no clawd receipt, so it may not hold {}",
+            path.display(),
+            skill_id
+        ));
+    }
+    if !dependents.is_empty() {
+        output.status(format_args!(
+            "⚠️  {} is required by: {}",
+            skill_id,
+            dependents.join(", ")
+        ));
+    }
+    if (unmanaged || !dependents.is_empty()) && !force && !prompt_confirm("Remove it anyway?")? {
+        output.status("❌ Removal cancelled");
+        output.print(&RemoveDocument {
+            id: skill_id,
+            status: RemoveStatus::Cancelled,
+            scope,
+            path,
+            unmanaged,
+            dependents,
+        })?;
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&path)?;
+    output.status(format_args!("🗑️  Removed {} from {:?}", skill_id, path));
+
+    output.print(&RemoveDocument {
+        id: skill_id,
+        status: RemoveStatus::Removed,
+        scope,
+        path,
+        unmanaged,
+        dependents,
+    })?;
+
+    Ok(())
+}
+
+/// Where `skill_id` is installed in `skills_dir`, given the skills `installed`
+/// there. Receipts find skills installed with --as; without one, the
+/// directory the naming strategy would pick counts for skills installed
+/// before receipts existed.
+pub fn find_installed(
+    config: &Config,
+    skills_dir: &Path,
+    installed: &[(PathBuf, InstallReceipt)],
+    registry: Option<&str>,
+    skill_id: &str,
+) -> Option<PathBuf> {
+    installed
+        .iter()
+        .find(|(_, receipt)| {
+            receipt.id == skill_id
+                && registry.is_none_or(|name| {
+                    name == receipt.registry.as_deref().unwrap_or(DEFAULT_REGISTRY)
+                })
+        })
+        .map(|(path, _)| path.clone())
+        .or_else(|| {
+            let (author, name) = parse_skill_id(skill_id).ok()?;
+            let path = skills_dir.join(config.install_dir_name(&author, &name, None));
+            (path.is_dir() && InstallReceipt::read(&path).is_none()).then_some(path)
+        })
+}
+
+/// The skills among `installed` that were installed to depend on `skill_id`.
+pub fn dependents(installed: &[(PathBuf, InstallReceipt)], skill_id: &str) -> Vec<String> {
+    installed
+        .iter()
+        .filter(|(_, receipt)| receipt.dependencies.iter().any(|id| id == skill_id))
+        .map(|(_, receipt)| receipt.id.clone())
+        .collect()
+}
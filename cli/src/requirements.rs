@@ -0,0 +1,353 @@
+use crate::api::clawd::Requirements;
+use semver::{Version, VersionReq};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The outcome of checking one requirement on this machine.
+pub struct Check {
+    /// What was checked, e.g. `python package pypdf>=3`
+    pub label: String,
+    /// The version found, when there was one to find
+    pub found: Option<String>,
+    /// Why the requirement isn't met; `None` when it is
+    pub problem: Option<String>,
+}
+
+impl Check {
+    pub fn is_met(&self) -> bool {
+        self.problem.is_none()
+    }
+}
+
+/// Checks a skill's declared system requirements: `bash_version`, commands
+/// on PATH and Python/Node packages, each with an optional version range.
+pub fn check(requirements: &Requirements) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if let Some(range) = &requirements.bash_version {
+        checks.push(check_version(
+            format!("bash {}", range),
+            Some(range),
+            find_on_path("bash").and_then(|path| command_version(&path)),
+            "bash not found on PATH",
+        ));
+    }
+
+    for spec in &requirements.commands {
+        let (name, range) = split_spec(spec);
+        // Only run the command when there's a version to compare; the names
+        // come from registry metadata, so presence is checked on PATH alone.
+        let found = find_on_path(name).map(|path| match range {
+            Some(_) => command_version(&path).unwrap_or_default(),
+            None => String::new(),
+        });
+        checks.push(check_version(
+            format!("command {}", spec),
+            range,
+            found,
+            "not found on PATH",
+        ));
+    }
+
+    for spec in &requirements.python_packages {
+        let (name, range) = split_spec(spec);
+        checks.push(check_version(
+            format!("python package {}", spec),
+            range,
+            python_package_version(name),
+            "not installed",
+        ));
+    }
+
+    for spec in &requirements.node_packages {
+        let (name, range) = split_spec(spec);
+        checks.push(check_version(
+            format!("node package {}", spec),
+            range,
+            node_package_version(name),
+            "not installed",
+        ));
+    }
+
+    checks
+}
+
+/// `found` is `Some("")` for something present whose version couldn't be
+/// determined, which satisfies requirements without a range.
+fn check_version(
+    label: String,
+    range: Option<&str>,
+    found: Option<String>,
+    missing: &str,
+) -> Check {
+    let problem = match (&found, range) {
+        (None, _) => Some(missing.to_string()),
+        (Some(_), None) => None,
+        (Some(version), Some(range)) => match parse_range(range) {
+            Err(e) => Some(format!("invalid version range '{}': {}", range, e)),
+            Ok(req) => match parse_version(version) {
+                None => Some("installed, but its version couldn't be determined".to_string()),
+                Some(v) if req.matches(&v) => None,
+                Some(_) => Some(format!("found {}", version)),
+            },
+        },
+    };
+
+    Check {
+        label,
+        found: found.filter(|v| !v.is_empty()),
+        problem,
+    }
+}
+
+/// Splits `pypdf>=3.0`, `node@^18` or `pdftotext` into a name and range.
+fn split_spec(spec: &str) -> (&str, Option<&str>) {
+    let spec = spec.trim();
+    // Skip the first character so scoped npm packages (@scope/pkg) keep their @.
+    let split = spec
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| matches!(c, '<' | '>' | '=' | '^' | '~' | '@' | ' '))
+        .map(|(i, _)| i);
+
+    match split {
+        Some(i) => {
+            let range = spec[i..].trim_start_matches('@').trim();
+            (spec[..i].trim(), (!range.is_empty()).then_some(range))
+        }
+        None => (spec, None),
+    }
+}
+
+/// A bare version such as `4.0` means "at least", matching how
+/// `bash_version` is written in registry entries. Python's `==X` (exactly)
+/// and `~=X.Y` (compatible release) are accepted too.
+fn parse_range(range: &str) -> Result<VersionReq, String> {
+    let range = range.trim();
+    let range = if let Some(version) = range.strip_prefix("~=") {
+        compatible_release(version.trim())?
+    } else if let Some(version) = range.strip_prefix("==") {
+        format!("={}", version.trim())
+    } else if range.starts_with(|c: char| c.is_ascii_digit()) {
+        format!(">={}", range)
+    } else {
+        range.to_string()
+    };
+    VersionReq::parse(&range).map_err(|e| e.to_string())
+}
+
+/// `~=1.4.2` is `>=1.4.2, <1.5` and `~=2.2` is `>=2.2, <3`, per PEP 440.
+fn compatible_release(version: &str) -> Result<String, String> {
+    let parts: Vec<&str> = version.split('.').collect();
+    if parts.len() < 2 {
+        return Err("~= needs at least two version components, e.g. ~=2.2".to_string());
+    }
+    let mut upper: Vec<u64> = parts[..parts.len() - 1]
+        .iter()
+        .map(|part| part.parse::<u64>().map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
+    *upper.last_mut().expect("at least one component") += 1;
+    let upper: Vec<String> = upper.iter().map(u64::to_string).collect();
+    Ok(format!(">={}, <{}", version, upper.join(".")))
+}
+
+/// Parses the first version-looking token, padding `3.10` to `3.10.0` and
+/// dropping suffixes such as `(1)-release` or `b1`.
+fn parse_version(text: &str) -> Option<Version> {
+    let tokens: Vec<&str> = text
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|token| token.trim_matches('.'))
+        .filter(|token| !token.is_empty())
+        .collect();
+    tokens
+        .iter()
+        .find(|token| token.contains('.'))
+        .or(tokens.first())
+        .and_then(|token| {
+            let mut parts: Vec<&str> = token.split('.').take(3).collect();
+            while parts.len() < 3 {
+                parts.push("0");
+            }
+            let parts: Vec<String> = parts
+                .iter()
+                .map(|p| p.trim_start_matches('0'))
+                .map(|p| if p.is_empty() { "0" } else { p }.to_string())
+                .collect();
+            Version::parse(&parts.join(".")).ok()
+        })
+}
+
+/// The executable `name` resolves to on PATH. Names with a path separator
+/// are never looked up, so a requirement can't point outside PATH.
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return None;
+    }
+    let paths = env::var_os("PATH")?;
+
+    env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(name);
+        if is_executable(&candidate) {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension("exe");
+        (cfg!(windows) && is_executable(&exe)).then_some(exe)
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// The version `<path> --version` reports, as the first line containing one.
+fn command_version(path: &Path) -> Option<String> {
+    let output = Command::new(path).arg("--version").output().ok()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let version = text
+        .lines()
+        .find_map(|line| {
+            line.split_whitespace()
+                .map(|word| word.trim_start_matches('v').trim_end_matches(','))
+                .find(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+        })
+        .unwrap_or_default();
+    Some(version.to_string())
+}
+
+fn python_package_version(name: &str) -> Option<String> {
+    let script = "import sys, importlib.metadata as m; print(m.version(sys.argv[1]))";
+    ["python3", "python"].iter().find_map(|python| {
+        let output = Command::new(python)
+            .args(["-c", script, name])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    })
+}
+
+/// Looks in the current project first, then in global packages.
+fn node_package_version(name: &str) -> Option<String> {
+    [&[][..], &["-g"][..]].iter().find_map(|extra| {
+        let output = Command::new(if cfg!(windows) { "npm.cmd" } else { "npm" })
+            .args(["ls", name, "--depth=0", "--json"])
+            .args(*extra)
+            .output()
+            .ok()?;
+        let tree: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        tree.get("dependencies")?
+            .get(name)?
+            .get("version")?
+            .as_str()
+            .map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_matches() {
+        let cases = [
+            // Bare versions mean "at least"
+            ("4.0", "5.2.15", true),
+            ("4.0", "4.0.0", true),
+            ("4.0", "3.2.57", false),
+            (">=3.0", "3.0.0", true),
+            ("<2", "2.0.0", false),
+            // Python's exact match, full and partial
+            ("==1.2.3", "1.2.3", true),
+            ("==1.2.3", "1.2.4", false),
+            ("==1.2", "1.2.9", true),
+            ("==1.2", "1.3.0", false),
+            // PEP 440 compatible release
+            ("~=1.4.2", "1.4.2", true),
+            ("~=1.4.2", "1.4.9", true),
+            ("~=1.4.2", "1.4.1", false),
+            ("~=1.4.2", "1.5.0", false),
+            ("~=2.2", "2.9.0", true),
+            ("~=2.2", "3.0.0", false),
+            // Cargo-style caret and tilde
+            ("^1", "1.9.0", true),
+            ("^1", "2.0.0", false),
+            ("^0.2", "0.2.5", true),
+            ("^0.2", "0.3.0", false),
+            ("~1.2", "1.2.7", true),
+            ("~1.2", "1.3.0", false),
+            (" ^18 ", "18.19.0", true),
+        ];
+        for (range, version, expected) in cases {
+            let req = parse_range(range).unwrap_or_else(|e| panic!("{}: {}", range, e));
+            let version = Version::parse(version).unwrap();
+            assert_eq!(req.matches(&version), expected, "{} vs {}", range, version);
+        }
+    }
+
+    #[test]
+    fn parse_range_rejects_garbage() {
+        for range in ["~=2", "~=a.b", "garbage", ">=", "1.2.3.4.5"] {
+            assert!(parse_range(range).is_err(), "{} should not parse", range);
+        }
+    }
+
+    #[test]
+    fn compatible_release_bounds() {
+        assert_eq!(compatible_release("1.4.2").unwrap(), ">=1.4.2, <1.5");
+        assert_eq!(compatible_release("2.2").unwrap(), ">=2.2, <3");
+        assert!(compatible_release("2").is_err());
+    }
+
+    #[test]
+    fn split_spec_names_and_ranges() {
+        let cases = [
+            ("pdftotext", ("pdftotext", None)),
+            ("pypdf>=3.0", ("pypdf", Some(">=3.0"))),
+            ("numpy~=1.24", ("numpy", Some("~=1.24"))),
+            ("python ==3.11", ("python", Some("==3.11"))),
+            ("node@^18", ("node", Some("^18"))),
+            ("jq@", ("jq", None)),
+            ("@scope/pkg", ("@scope/pkg", None)),
+            ("@scope/pkg@1.2", ("@scope/pkg", Some("1.2"))),
+            ("  rg  ", ("rg", None)),
+        ];
+        for (spec, expected) in cases {
+            assert_eq!(split_spec(spec), expected, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn parse_version_from_command_output() {
+        let cases = [
+            ("GNU bash, version 5.2.15(1)-release", Some("5.2.15")),
+            ("Python 3.10", Some("3.10.0")),
+            ("v18.19.0", Some("18.19.0")),
+            ("jq-1.7.1", Some("1.7.1")),
+            ("git version 2.43.0", Some("2.43.0")),
+            ("Python 3.12.0b1", Some("3.12.0")),
+            ("1.02.3", Some("1.2.3")),
+            ("openssl 3", Some("3.0.0")),
+            ("no version here", None),
+        ];
+        for (text, expected) in cases {
+            let expected = expected.map(|v| Version::parse(v).unwrap());
+            assert_eq!(parse_version(text), expected, "{}", text);
+        }
+    }
+}
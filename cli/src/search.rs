@@ -0,0 +1,323 @@
+use crate::api::clawd::{ListResponse, SkillFilters, SkillResponse};
+use crate::config::Config;
+use crate::error::ClawdError;
+use crate::index::SkillIndex;
+use crate::list::{merge_pages, print_skills_table};
+use crate::output::OutputFormat;
+use crate::registry::{Registries, Registry};
+use std::path::Path;
+use std::time::Duration;
+
+/// Where search results come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Ask the registry, falling back to the local index if it can't search
+    Remote,
+    /// Rank locally over the cached index, refreshing it when stale
+    Local,
+    /// Rank locally over the cached index without touching the network
+    Offline,
+}
+
+pub async fn execute_search(
+    query: String,
+    page: u32,
+    limit: u32,
+    filters: SkillFilters,
+    mode: SearchMode,
+    api_url: Option<String>,
+    output: OutputFormat,
+) -> Result<(), ClawdError> {
+    let config =
+        Config::new(api_url).map_err(|e| ClawdError::InvalidResponse(format!("{:#}", e)))?;
+    let registries = Registries::new(&config);
+
+    let terms = query_terms(&query);
+
+    // A fresh local index ranks the whole catalog, where re-ranking what the
+    // registries return can only order each page.
+    let indexed = registries.iter().all(|registry| {
+        registry.client.is_local()
+            || SkillIndex::load(&config.cache_dir, registry.client.base_url())
+                .is_some_and(|index| !index.is_stale())
+    });
+
+    if mode != SearchMode::Remote || indexed {
+        let offline = mode == SearchMode::Offline;
+        let all: Vec<&Registry> = registries.iter().collect();
+        let index =
+            load_indexes(&config.cache_dir, &all, registries.is_multiple(), offline).await?;
+        return search_local(&index, &query, page, limit, &filters, &registries, output);
+    }
+
+    let mut responses = Vec::new();
+    let mut unsupported = Vec::new();
+    let mut last_error = None;
+    for registry in registries.iter() {
+        match registry
+            .client
+            .search_skills(&query, page, limit, &filters)
+            .await
+        {
+            Ok(mut response) => {
+                for skill in &mut response.skills {
+                    skill.registry = Some(registry.name.clone());
+                }
+                responses.push(response);
+            }
+            // Static registries can't search; that's no news to their users.
+            Err(ClawdError::SearchUnsupported(_)) if registry.client.is_static() => {
+                unsupported.push(registry);
+            }
+            Err(ClawdError::SearchUnsupported(_)) => {
+                if registries.is_multiple() {
+                    eprintln!(
+                        "Registry {} has no search endpoint, searching its local index instead.",
+                        registry.name
+                    );
+                } else {
+                    eprintln!(
+                        "Registry has no search endpoint, searching the local index instead."
+                    );
+                }
+                unsupported.push(registry);
+            }
+            Err(e) => {
+                if registries.is_multiple() {
+                    eprintln!("⚠️  Skipping registry {}: {}", registry.name, e);
+                }
+                last_error = Some(e);
+            }
+        }
+    }
+
+    if !unsupported.is_empty() {
+        let index = load_indexes(
+            &config.cache_dir,
+            &unsupported,
+            registries.is_multiple(),
+            false,
+        )
+        .await?;
+        if responses.is_empty() {
+            return search_local(&index, &query, page, limit, &filters, &registries, output);
+        }
+        // Rank the index the same way a registry would and contribute the
+        // same page of it.
+        let results = index
+            .search(&terms)
+            .into_iter()
+            .filter(|s| filters.matches(s))
+            .cloned()
+            .collect();
+        responses.push(paginate(results, page, limit));
+    }
+    if responses.is_empty() {
+        return Err(last_error.expect("at least one registry is configured"));
+    }
+
+    let response = merge_pages(responses);
+    let skills: Vec<SkillResponse> = response
+        .skills
+        .into_iter()
+        .filter(|s| filters.matches(s))
+        .collect();
+    let mut skills = rank_skills(skills, &terms);
+    filters.sort(&mut skills);
+
+    if output.is_structured() {
+        return output.print(&ListResponse { skills, ..response });
+    }
+
+    if skills.is_empty() {
+        println!("No skills found matching '{}'.", query);
+        return Ok(());
+    }
+
+    print_skills_table(&skills, &terms, &registries, output == OutputFormat::Wide);
+
+    println!();
+    println!(
+        "Page {} of {} ({} matching skills)",
+        response.page, response.total_pages, response.total
+    );
+
+    Ok(())
+}
+
+/// The cached indexes of `registries`, refreshed as in
+/// `SkillIndex::load_or_fetch` and combined into one, with every skill tagged
+/// with its registry. With `skip_failures`, a registry whose index can't be
+/// had is left out with a warning unless none can.
+pub async fn load_indexes(
+    cache_dir: &Path,
+    registries: &[&Registry],
+    skip_failures: bool,
+    offline: bool,
+) -> Result<SkillIndex, ClawdError> {
+    let mut indexes = Vec::new();
+    let mut last_error = None;
+    for registry in registries {
+        match SkillIndex::load_or_fetch(cache_dir, &registry.client, offline).await {
+            Ok(mut index) => {
+                for entry in &mut index.skills {
+                    entry.skill.registry = Some(registry.name.clone());
+                }
+                indexes.push(index);
+            }
+            Err(e) if skip_failures => {
+                eprintln!("⚠️  Skipping registry {}: {}", registry.name, e);
+                last_error = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    match SkillIndex::merge(indexes) {
+        Some(index) => Ok(index),
+        None => Err(last_error.unwrap_or(ClawdError::NoLocalIndex)),
+    }
+}
+
+/// Ranks skills from the cached registry index and prints the requested page.
+fn search_local(
+    index: &SkillIndex,
+    query: &str,
+    page: u32,
+    limit: u32,
+    filters: &SkillFilters,
+    registries: &Registries,
+    output: OutputFormat,
+) -> Result<(), ClawdError> {
+    let terms = query_terms(query);
+    let mut results: Vec<SkillResponse> = index
+        .search(&terms)
+        .into_iter()
+        .filter(|s| filters.matches(s))
+        .cloned()
+        .collect();
+    filters.sort(&mut results);
+
+    if results.is_empty() && !output.is_structured() {
+        println!("No skills found matching '{}'.", query);
+        return Ok(());
+    }
+
+    let response = paginate(results, page, limit);
+
+    if output.is_structured() {
+        return output.print(&response);
+    }
+
+    print_skills_table(
+        &response.skills,
+        &terms,
+        registries,
+        output == OutputFormat::Wide,
+    );
+
+    println!();
+    println!(
+        "Page {} of {} ({} matching skills, local index updated {} ago)",
+        response.page,
+        response.total_pages,
+        response.total,
+        format_age(index.age())
+    );
+
+    Ok(())
+}
+
+/// The requested page of `results`, clamped to the last page.
+pub fn paginate(results: Vec<SkillResponse>, page: u32, limit: u32) -> ListResponse {
+    let limit = limit.max(1) as usize;
+    let total = results.len();
+    let total_pages = total.div_ceil(limit);
+    let page = (page.max(1) as usize).min(total_pages).max(1);
+
+    let skills: Vec<SkillResponse> = results
+        .into_iter()
+        .skip((page - 1) * limit)
+        .take(limit)
+        .collect();
+
+    ListResponse {
+        skills,
+        total: total as u32,
+        page: page as u32,
+        limit: limit as u32,
+        total_pages: total_pages as u32,
+    }
+}
+
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 60 * 60 {
+        format!("{}m", secs / 60)
+    } else if secs < 24 * 60 * 60 {
+        format!("{}h", secs / (60 * 60))
+    } else {
+        format!("{}d", secs / (24 * 60 * 60))
+    }
+}
+
+pub fn query_terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(|t| t.to_lowercase()).collect()
+}
+
+/// Orders skills by how well they match `terms`, best first. Ties are broken
+/// by download count so popular skills float up among equal matches.
+pub fn rank_skills(skills: Vec<SkillResponse>, terms: &[String]) -> Vec<SkillResponse> {
+    let mut scored: Vec<(u32, SkillResponse)> = skills
+        .into_iter()
+        .map(|skill| (score_skill(&skill, terms), skill))
+        .collect();
+
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| b.download_count.cmp(&a.download_count))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    scored.into_iter().map(|(_, skill)| skill).collect()
+}
+
+/// Scores a skill against the query terms. Matches in the ID and title count
+/// most, then tags, then the description.
+fn score_skill(skill: &SkillResponse, terms: &[String]) -> u32 {
+    let id = skill.id.to_lowercase();
+    let name = id.split('/').nth(1).unwrap_or(&id);
+    let title = skill.title.to_lowercase();
+    let description = skill.description.to_lowercase();
+    let tags: Vec<String> = skill.tags.iter().map(|t| t.to_lowercase()).collect();
+
+    let mut score = 0;
+    for term in terms {
+        if name == term {
+            score += 100;
+        } else if id.contains(term.as_str()) {
+            score += 40;
+        }
+
+        if title == *term {
+            score += 50;
+        } else if title.contains(term.as_str()) {
+            score += 30;
+        }
+
+        if tags.iter().any(|t| t == term) {
+            score += 25;
+        } else if tags.iter().any(|t| t.contains(term.as_str())) {
+            score += 10;
+        }
+
+        if description.contains(term.as_str()) {
+            score += 5;
+        }
+    }
+
+    score
+}
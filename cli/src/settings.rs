@@ -0,0 +1,831 @@
+use crate::config::{
+    self, InstallScope, NamingStrategy, DEFAULT_API_URL, DEFAULT_PARALLELISM, DEFAULT_REGISTRY,
+    DEFAULT_REGISTRY_PRIORITY,
+};
+use crate::output::OutputFormat;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Settings read from config files and the environment. Every field is
+/// optional; `Config` fills in the defaults.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub api_url: Option<String>,
+    pub api_token: Option<String>,
+    pub github_token: Option<String>,
+    pub scope: Option<InstallScope>,
+    pub naming: Option<NamingStrategy>,
+    pub output: Option<OutputFormat>,
+    pub cache_dir: Option<PathBuf>,
+    pub skills_dir: Option<PathBuf>,
+    pub parallelism: Option<usize>,
+    pub update_check: Option<bool>,
+    #[serde(default)]
+    pub policy: Policy,
+    #[serde(default)]
+    pub registries: BTreeMap<String, RegistrySettings>,
+}
+
+/// Rules `clawd add` enforces before installing anything.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    /// Refuse skills that aren't verified by the Clawd team
+    pub verified_only: Option<bool>,
+    /// Install even when system requirements aren't met
+    pub ignore_requirements: Option<bool>,
+}
+
+/// A `[registries.<name>]` table declaring a registry besides the public
+/// one, which is named `clawd`.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RegistrySettings {
+    pub url: Option<String>,
+    pub token: Option<String>,
+    /// Environment variable holding the token, so it stays out of the file
+    pub token_env: Option<String>,
+    /// Registries are tried lowest first for unqualified skill IDs
+    pub priority: Option<i64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Path,
+    Integer,
+    Bool,
+}
+
+/// A setting as it's addressed by `clawd config` and the environment.
+struct Key {
+    name: &'static str,
+    env: &'static str,
+    kind: Kind,
+    secret: bool,
+    description: &'static str,
+    /// Shown commented out in new config files
+    example: &'static str,
+}
+
+const KEYS: &[Key] = &[
+    Key {
+        name: "api_url",
+        env: "CLAWD_API_URL",
+        kind: Kind::String,
+        secret: false,
+        description: "Registry API URL",
+        example: r#""https://api.clawd.xyz""#,
+    },
+    Key {
+        name: "api_token",
+        env: "CLAWD_API_TOKEN",
+        kind: Kind::String,
+        secret: true,
+        description: "Bearer token sent to the registry API",
+        example: r#""""#,
+    },
+    Key {
+        name: "github_token",
+        env: "CLAWD_GITHUB_TOKEN",
+        kind: Kind::String,
+        secret: true,
+        description: "GitHub token for downloads, raising the API rate limit",
+        example: r#""""#,
+    },
+    Key {
+        name: "scope",
+        env: "CLAWD_SCOPE",
+        kind: Kind::String,
+        secret: false,
+        description: "Default install scope: user or project",
+        example: r#""user""#,
+    },
+    Key {
+        name: "naming",
+        env: "CLAWD_NAMING",
+        kind: Kind::String,
+        secret: false,
+        description: "Install directory naming: name or author-name",
+        example: r#""name""#,
+    },
+    Key {
+        name: "output",
+        env: "CLAWD_OUTPUT",
+        kind: Kind::String,
+        secret: false,
+        description: "Default output format: table, wide, json or yaml",
+        example: r#""table""#,
+    },
+    Key {
+        name: "cache_dir",
+        env: "CLAWD_CACHE_DIR",
+        kind: Kind::Path,
+        secret: false,
+        description: "Where the registry index is cached",
+        example: r#""~/.cache/clawd""#,
+    },
+    Key {
+        name: "skills_dir",
+        env: "CLAWD_SKILLS_DIR",
+        kind: Kind::Path,
+        secret: false,
+        description: "User-scope skills directory",
+        example: r#""~/.claude/skills""#,
+    },
+    Key {
+        name: "parallelism",
+        env: "CLAWD_PARALLELISM",
+        kind: Kind::Integer,
+        secret: false,
+        description: "Files downloaded at once",
+        example: "4",
+    },
+    Key {
+        name: "update_check",
+        env: "CLAWD_UPDATE_CHECK",
+        kind: Kind::Bool,
+        secret: false,
+        description: "Check for a new release once a day",
+        example: "true",
+    },
+    Key {
+        name: "policy.verified_only",
+        env: "CLAWD_VERIFIED_ONLY",
+        kind: Kind::Bool,
+        secret: false,
+        description: "Only install verified skills",
+        example: "false",
+    },
+    Key {
+        name: "policy.ignore_requirements",
+        env: "CLAWD_IGNORE_REQUIREMENTS",
+        kind: Kind::Bool,
+        secret: false,
+        description: "Install even when system requirements aren't met",
+        example: "false",
+    },
+];
+
+/// Settings of each declared registry, addressed as `registries.<name>.<key>`.
+const REGISTRY_KEYS: &[Key] = &[
+    Key {
+        name: "url",
+        env: "",
+        kind: Kind::String,
+        secret: false,
+        description: "Registry API URL",
+        example: r#""https://skills.example.com""#,
+    },
+    Key {
+        name: "token",
+        env: "",
+        kind: Kind::String,
+        secret: true,
+        description: "Bearer token sent to the registry",
+        example: r#""""#,
+    },
+    Key {
+        name: "token_env",
+        env: "",
+        kind: Kind::String,
+        secret: false,
+        description: "Environment variable holding the token",
+        example: r#""INTERNAL_SKILLS_TOKEN""#,
+    },
+    Key {
+        name: "priority",
+        env: "",
+        kind: Kind::Integer,
+        secret: false,
+        description: "Lower is tried first for unqualified IDs (default 0, clawd 100)",
+        example: "0",
+    },
+];
+
+/// Settings `.claude/clawd.toml` may not change. That file comes with
+/// whatever repository is checked out, so it mustn't pick the URL the user's
+/// tokens are sent to, the tokens themselves, or registries that would shadow
+/// public skills.
+const USER_ONLY: &[&str] = &["api_url", "api_token", "github_token", "registries"];
+
+/// Where a setting's value came from, lowest precedence first.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+    /// Built in, when nothing else sets the value
+    Default,
+    System,
+    User,
+    Project,
+    Env,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Default => "default",
+            Self::System => "system",
+            Self::User => "user",
+            Self::Project => "project",
+            Self::Env => "env",
+        })
+    }
+}
+
+impl Layer {
+    /// The config file for this layer, or `None` for the defaults, the
+    /// environment and the project layer outside a project.
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Default => None,
+            Self::System => Some(system_config_path()),
+            Self::User => user_config_path(),
+            Self::Project => find_project_dir().map(|dir| dir.join("clawd.toml")),
+            Self::Env => None,
+        }
+    }
+}
+
+/// Every layer that sets something, in precedence order.
+pub struct LoadedSettings {
+    layers: Vec<(Layer, Option<PathBuf>, Table)>,
+}
+
+impl LoadedSettings {
+    /// Reads the system, user and project config files and the environment.
+    pub fn load() -> Result<Self> {
+        let mut layers = Vec::new();
+        for layer in [Layer::System, Layer::User, Layer::Project] {
+            let Some(path) = layer.path() else {
+                continue;
+            };
+            if let Some(table) = read_table(&path)? {
+                if layer == Layer::Project {
+                    check_project_table(&path, &table)?;
+                }
+                layers.push((layer, Some(path), table));
+            }
+        }
+        layers.push((Layer::Env, None, env_table()?));
+
+        let loaded = Self { layers };
+        // Check each layer on its own so a bad value fails every command
+        // naming the file it came from, not just the commands that use it.
+        for (layer, path, table) in &loaded.layers {
+            Value::Table(table.clone())
+                .try_into::<Settings>()
+                .with_context(|| match path {
+                    Some(path) => format!("Invalid config file {}", path.display()),
+                    None => format!("Invalid {} setting", layer),
+                })?;
+        }
+        Ok(loaded)
+    }
+
+    pub fn settings(&self) -> Settings {
+        let mut merged = Table::new();
+        for (_, _, table) in &self.layers {
+            merge(&mut merged, table.clone());
+        }
+        // Each layer was validated on load, and merging can't make it invalid.
+        Value::Table(merged).try_into().unwrap_or_default()
+    }
+
+    /// The effective value of `key` and the layer that set it, falling back
+    /// to the default.
+    fn get(&self, key: &str) -> Option<(Value, Layer)> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(layer, _, table)| lookup(table, key).map(|value| (value.clone(), *layer)))
+            .or_else(|| default_value(key).map(|value| (value, Layer::Default)))
+    }
+}
+
+/// What a setting is when nothing sets it, or `None` when unset means
+/// something else, like `scope`, which asks.
+fn default_value(name: &str) -> Option<Value> {
+    let path = |path: Result<PathBuf>| Some(Value::String(path.ok()?.display().to_string()));
+    match name {
+        "api_url" => Some(Value::String(DEFAULT_API_URL.to_string())),
+        "naming" => Some(Value::String("name".to_string())),
+        "output" => Some(Value::String("table".to_string())),
+        "cache_dir" => path(config::default_cache_dir()),
+        "skills_dir" => path(config::default_skills_dir()),
+        "parallelism" => Some(Value::Integer(DEFAULT_PARALLELISM as i64)),
+        "update_check" => Some(Value::Boolean(true)),
+        "policy.verified_only" | "policy.ignore_requirements" => Some(Value::Boolean(false)),
+        _ => {
+            let registry = name
+                .strip_prefix("registries.")?
+                .strip_suffix(".priority")?;
+            let priority = if registry == DEFAULT_REGISTRY {
+                DEFAULT_REGISTRY_PRIORITY
+            } else {
+                0
+            };
+            Some(Value::Integer(priority))
+        }
+    }
+}
+
+/// The output format from the config files and environment, for `main` to
+/// use before any command runs. A broken config is reported by the command.
+pub fn configured_output() -> Option<OutputFormat> {
+    LoadedSettings::load().ok()?.settings().output
+}
+
+/// Whether the config files and environment turn the update check on or off.
+pub fn configured_update_check() -> Option<bool> {
+    LoadedSettings::load().ok()?.settings().update_check
+}
+
+fn read_table(path: &Path) -> Result<Option<Table>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            let table = contents
+                .parse::<Table>()
+                .with_context(|| format!("Invalid config file {}", path.display()))?;
+            Ok(Some(table))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Cannot read {}", path.display())),
+    }
+}
+
+fn env_table() -> Result<Table> {
+    let mut table = Table::new();
+    for key in KEYS {
+        let value = env::var(key.env)
+            .ok()
+            .or_else(|| match key.name {
+                "github_token" => env::var("GITHUB_TOKEN").ok(),
+                _ => None,
+            })
+            .filter(|value| !value.is_empty());
+        if let Some(value) = value {
+            let value = parse_value(key, &value).with_context(|| format!("Invalid {}", key.env))?;
+            insert(&mut table, key.name, value);
+        }
+    }
+    Ok(table)
+}
+
+/// Converts a command-line or environment string to the key's TOML type.
+fn parse_value(key: &Key, value: &str) -> Result<Value> {
+    Ok(match key.kind {
+        Kind::String | Kind::Path => Value::String(value.to_string()),
+        Kind::Integer => Value::Integer(
+            value
+                .parse()
+                .with_context(|| format!("Expected a number, got '{}'", value))?,
+        ),
+        Kind::Bool => Value::Boolean(match value.to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => true,
+            "false" | "0" | "no" | "off" => false,
+            _ => bail!("Expected true or false, got '{}'", value),
+        }),
+    })
+}
+
+fn find_key(name: &str) -> Result<&'static Key> {
+    if let Some((registry, field)) = name
+        .strip_prefix("registries.")
+        .and_then(|rest| rest.split_once('.'))
+    {
+        if !is_valid_registry_name(registry) {
+            bail!(
+                "Invalid registry name '{}'. Use lowercase letters, digits and hyphens",
+                registry
+            );
+        }
+        return REGISTRY_KEYS
+            .iter()
+            .find(|key| key.name == field)
+            .with_context(|| {
+                format!(
+                    "Unknown registry setting '{}'. Expected url, token, token_env or priority",
+                    field
+                )
+            });
+    }
+
+    KEYS.iter().find(|key| key.name == name).with_context(|| {
+        format!(
+            "Unknown setting '{}'. Run `clawd config list` to see them all",
+            name
+        )
+    })
+}
+
+fn check_project_table(path: &Path, table: &Table) -> Result<()> {
+    if let Some(key) = table.keys().find(|key| is_user_only(key)) {
+        bail!(
+            "{} can't set {}, since project config comes with the repository. \
+             Set it in the user config or the environment instead",
+            path.display(),
+            key
+        );
+    }
+    Ok(())
+}
+
+/// Whether `name`, or the section it's in, is one of `USER_ONLY`.
+fn is_user_only(name: &str) -> bool {
+    let section = name.split('.').next().unwrap_or(name);
+    USER_ONLY.contains(&section)
+}
+
+pub fn is_valid_registry_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    match key.split_once('.') {
+        Some((section, rest)) => lookup(table.get(section)?.as_table()?, rest),
+        None => table.get(key),
+    }
+}
+
+fn insert(table: &mut Table, key: &str, value: Value) {
+    match key.split_once('.') {
+        Some((section, rest)) => {
+            let entry = table
+                .entry(section)
+                .or_insert_with(|| Value::Table(Table::new()));
+            if let Value::Table(section) = entry {
+                insert(section, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Overlays `overrides` onto `base`, merging sections key by key.
+fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// `~/...` in a configured path means the home directory.
+pub fn expand_home(path: PathBuf) -> PathBuf {
+    let home = directories::UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    match (path.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
+fn system_config_path() -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string()))
+            .join("clawd")
+            .join("config.toml")
+    } else {
+        PathBuf::from("/etc/clawd/config.toml")
+    }
+}
+
+/// `~/.config/clawd/config.toml`, honoring `XDG_CONFIG_HOME`. Windows uses
+/// its roaming app data directory instead.
+fn user_config_path() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return directories::ProjectDirs::from("", "", "clawd")
+            .map(|dirs| dirs.config_dir().join("config.toml"));
+    }
+
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| directories::UserDirs::new().map(|dirs| dirs.home_dir().join(".config")))?;
+    Some(config_home.join("clawd").join("config.toml"))
+}
+
+/// The nearest `.claude` directory in the current directory or its parents.
+pub fn find_project_dir() -> Option<PathBuf> {
+    let current_dir = env::current_dir().ok()?;
+    current_dir
+        .ancestors()
+        .map(|dir| dir.join(".claude"))
+        .find(|dir| dir.is_dir())
+}
+
+#[derive(Serialize)]
+struct SettingDocument {
+    key: String,
+    value: Option<Value>,
+    source: Option<Layer>,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    env: &'static str,
+    description: &'static str,
+}
+
+impl SettingDocument {
+    /// `name` is the full setting name, which for registry settings is
+    /// longer than `key.name`.
+    fn new(name: &str, key: &'static Key, loaded: &LoadedSettings) -> Self {
+        let (value, source) = match loaded.get(name) {
+            Some((value, layer)) => (Some(value), Some(layer)),
+            None => (None, None),
+        };
+        Self {
+            key: name.to_string(),
+            value,
+            source,
+            env: key.env,
+            description: key.description,
+        }
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn mask(value: &str) -> String {
+    let tail: String = value
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    format!("****{}", tail)
+}
+
+pub fn execute_config_get(name: String, output: OutputFormat) -> Result<()> {
+    let key = find_key(&name)?;
+    let loaded = LoadedSettings::load()?;
+    let document = SettingDocument::new(&name, key, &loaded);
+
+    if output.is_structured() {
+        output.print(&document)?;
+        return Ok(());
+    }
+
+    match &document.value {
+        Some(value) => println!("{}", display_value(value)),
+        None => bail!("{} is not set", name),
+    }
+    Ok(())
+}
+
+pub fn execute_config_list(output: OutputFormat) -> Result<()> {
+    let loaded = LoadedSettings::load()?;
+    let mut keys: Vec<(String, &'static Key)> =
+        KEYS.iter().map(|key| (key.name.to_string(), key)).collect();
+    for registry in loaded.settings().registries.keys() {
+        keys.extend(
+            REGISTRY_KEYS
+                .iter()
+                .map(|key| (format!("registries.{}.{}", registry, key.name), key))
+                .filter(|(name, _)| {
+                    loaded
+                        .get(name)
+                        .is_some_and(|(_, layer)| layer != Layer::Default)
+                }),
+        );
+    }
+    let documents: Vec<SettingDocument> = keys
+        .iter()
+        .map(|(name, key)| SettingDocument::new(name, key, &loaded))
+        .collect();
+
+    if output.is_structured() {
+        // Tokens stay out of structured output so it can go in bug reports.
+        let documents: Vec<SettingDocument> = documents
+            .into_iter()
+            .zip(&keys)
+            .map(|(mut document, (_, key))| {
+                if key.secret {
+                    document.value = document
+                        .value
+                        .map(|value| Value::String(mask(&display_value(&value))));
+                }
+                document
+            })
+            .collect();
+        output.print(&documents)?;
+        return Ok(());
+    }
+
+    let mut table = crate::table::Table::new(vec![
+        crate::table::Column::new("KEY"),
+        crate::table::Column::new("VALUE").flexible(10),
+        crate::table::Column::new("SOURCE"),
+        crate::table::Column::new("DESCRIPTION").flexible(20),
+    ])
+    .wide(output == OutputFormat::Wide);
+    for (document, (_, key)) in documents.iter().zip(&keys) {
+        let value = match &document.value {
+            Some(value) if key.secret => mask(&display_value(value)),
+            Some(value) => display_value(value),
+            None => "-".to_string(),
+        };
+        let source = match document.source {
+            Some(layer) => layer.to_string(),
+            None => "-".to_string(),
+        };
+        table.add_row(vec![
+            document.key.clone(),
+            value,
+            source,
+            document.description.to_string(),
+        ]);
+    }
+    table.print();
+
+    println!();
+    for layer in [Layer::System, Layer::User, Layer::Project] {
+        match layer.path() {
+            Some(path) => println!("{:<8} {}", layer, path.display()),
+            None => println!("{:<8} (not in a project)", layer),
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_config_set(
+    name: String,
+    value: String,
+    layer: Layer,
+    output: OutputFormat,
+) -> Result<()> {
+    let key = find_key(&name)?;
+    if layer == Layer::Project && is_user_only(&name) {
+        bail!(
+            "{} can't be set per project, since project config comes with the \
+             repository. Leave out --project to set it in the user config",
+            name
+        );
+    }
+    let path = file_for(layer)?;
+    let parsed = parse_value(key, &value)?;
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Cannot read {}", path.display())),
+    };
+    let mut document = contents
+        .parse::<toml_edit::DocumentMut>()
+        .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+    let item = toml_edit::value(match &parsed {
+        Value::String(s) => toml_edit::Value::from(s.as_str()),
+        Value::Integer(i) => toml_edit::Value::from(*i),
+        Value::Boolean(b) => toml_edit::Value::from(*b),
+        _ => unreachable!("parse_value only produces scalars"),
+    });
+    let mut sections: Vec<&str> = name.split('.').collect();
+    let field = sections.pop().expect("split yields at least one part");
+    let mut table = document.as_table_mut();
+    for section in sections {
+        table = table
+            .entry(section)
+            .or_insert_with(|| {
+                // Only the innermost section gets a [header], e.g.
+                // [registries.internal] rather than an empty [registries].
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+            .as_table_mut()
+            .with_context(|| format!("'{}' in {} is not a table", section, path.display()))?;
+    }
+    table[field] = item;
+
+    // Validate before writing so a typo never leaves a config that breaks
+    // every command.
+    let updated = document.to_string();
+    let table = updated.parse::<Table>()?;
+    Value::Table(table)
+        .try_into::<Settings>()
+        .with_context(|| format!("Invalid value for {}", name))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, updated).with_context(|| format!("Cannot write {}", path.display()))?;
+
+    output.status(format_args!(
+        "✅ Set {} = {} in {}",
+        name,
+        if key.secret {
+            mask(&value)
+        } else {
+            display_value(&parsed)
+        },
+        path.display()
+    ));
+    output.print(&SettingDocument::new(&name, key, &LoadedSettings::load()?))?;
+    Ok(())
+}
+
+pub fn execute_config_edit(layer: Layer) -> Result<()> {
+    let path = file_for(layer)?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, template()).with_context(|| format!("Cannot write {}", path.display()))?;
+    }
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    // The editor may come with arguments, e.g. "code --wait".
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("$EDITOR is empty")?;
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'", editor))?;
+    if !status.success() {
+        bail!("Editor '{}' exited with {}", editor, status);
+    }
+
+    if let Some(table) = read_table(&path)? {
+        if layer == Layer::Project {
+            check_project_table(&path, &table)?;
+        }
+        Value::Table(table)
+            .try_into::<Settings>()
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+    }
+    println!("✅ {} is valid", path.display());
+    Ok(())
+}
+
+fn file_for(layer: Layer) -> Result<PathBuf> {
+    match layer {
+        Layer::Default => bail!("Defaults can't be edited"),
+        Layer::Env => bail!("Environment variables can't be edited by clawd"),
+        Layer::Project => layer.path().context(
+            "No .claude directory in the current directory or its parents. Create one first",
+        ),
+        _ => layer.path().context("Could not find the config directory"),
+    }
+}
+
+/// A new config file listing every setting, commented out.
+fn template() -> String {
+    let mut contents = String::from("# clawd configuration. Uncomment a setting to change it.\n");
+    let mut section = None;
+    for key in KEYS {
+        let (key_section, name) = match key.name.split_once('.') {
+            Some((section, name)) => (Some(section), name),
+            None => (None, key.name),
+        };
+        if key_section != section {
+            if let Some(key_section) = key_section {
+                contents.push_str(&format!("\n[{}]\n", key_section));
+            }
+            section = key_section;
+        }
+        contents.push_str(&format!(
+            "\n# {} (env: {})\n# {} = {}\n",
+            key.description, key.env, name, key.example
+        ));
+    }
+
+    contents.push_str(
+        "\n# Registries besides the public one (named clawd), tried in priority order\n\
+         # for skill IDs without a registry: prefix\n# [registries.internal]\n",
+    );
+    for key in REGISTRY_KEYS {
+        contents.push_str(&format!("# {} = {}\n", key.name, key.example));
+    }
+    contents
+}
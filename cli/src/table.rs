@@ -0,0 +1,405 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Width used when stdout isn't a terminal and no size can be detected.
+const DEFAULT_WIDTH: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+pub struct Column {
+    header: &'static str,
+    align: Align,
+    min_width: usize,
+    /// Flexible columns give up width when the table doesn't fit
+    flexible: bool,
+    /// Wrapped columns spill onto extra lines instead of being truncated
+    wrap: bool,
+    highlight: bool,
+}
+
+impl Column {
+    pub fn new(header: &'static str) -> Self {
+        Self {
+            header,
+            align: Align::Left,
+            min_width: UnicodeWidthStr::width(header),
+            flexible: false,
+            wrap: false,
+            highlight: false,
+        }
+    }
+
+    pub fn right(mut self) -> Self {
+        self.align = Align::Right;
+        self
+    }
+
+    pub fn flexible(mut self, min_width: usize) -> Self {
+        self.flexible = true;
+        self.min_width = self.min_width.max(min_width);
+        self
+    }
+
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    pub fn highlight(mut self) -> Self {
+        self.highlight = true;
+        self
+    }
+}
+
+/// A table that measures cells by display width, so CJK and emoji line up,
+/// and fits itself to the terminal by shrinking flexible columns.
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    highlight_terms: Vec<String>,
+    wide: bool,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            highlight_terms: Vec::new(),
+            wide: false,
+        }
+    }
+
+    /// Highlights occurrences of `terms` in columns marked with
+    /// [`Column::highlight`] when colors are enabled.
+    pub fn highlight_terms(mut self, terms: &[String]) -> Self {
+        self.highlight_terms = terms.to_vec();
+        self
+    }
+
+    /// Renders every cell at its full width instead of fitting the terminal.
+    pub fn wide(mut self, wide: bool) -> Self {
+        self.wide = wide;
+        self
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn print(&self) {
+        let width = console::Term::stdout()
+            .size_checked()
+            .map(|(_, cols)| cols as usize)
+            .unwrap_or(DEFAULT_WIDTH);
+
+        for line in self.render(width) {
+            println!("{}", line);
+        }
+    }
+
+    pub fn render(&self, width: usize) -> Vec<String> {
+        let widths = self.column_widths(width);
+        let mut lines = Vec::new();
+
+        let header: Vec<String> = self.columns.iter().map(|c| c.header.to_string()).collect();
+        lines.push(self.render_cells(&header, &widths, false));
+        let total = widths.iter().sum::<usize>() + widths.len().saturating_sub(1);
+        lines.push("-".repeat(total));
+
+        for row in &self.rows {
+            let cells: Vec<Vec<String>> = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (column, &w))| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or("");
+                    if column.wrap && !self.wide {
+                        wrap(cell, w)
+                    } else if self.wide {
+                        vec![cell.to_string()]
+                    } else {
+                        vec![truncate(cell, w)]
+                    }
+                })
+                .collect();
+
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+            for line in 0..height {
+                let parts: Vec<String> = cells
+                    .iter()
+                    .map(|c| c.get(line).cloned().unwrap_or_default())
+                    .collect();
+                lines.push(self.render_cells(&parts, &widths, true));
+            }
+        }
+
+        lines
+    }
+
+    fn render_cells(&self, cells: &[String], widths: &[usize], highlight: bool) -> String {
+        let parts: Vec<String> = self
+            .columns
+            .iter()
+            .zip(cells)
+            .zip(widths)
+            .map(|((column, cell), &w)| {
+                let padded = pad(cell, w, column.align);
+                if highlight && column.highlight {
+                    highlight_matches(&padded, &self.highlight_terms)
+                } else {
+                    padded
+                }
+            })
+            .collect();
+
+        parts.join(" ").trim_end().to_string()
+    }
+
+    fn column_widths(&self, width: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| UnicodeWidthStr::width(cell.as_str()))
+                    .chain(std::iter::once(UnicodeWidthStr::width(column.header)))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        if self.wide {
+            return widths;
+        }
+
+        let separators = self.columns.len().saturating_sub(1);
+        let available = width.saturating_sub(separators);
+
+        // Shrink one column at a time until the table fits. Wrapped columns
+        // give up width first since they lose nothing but extra lines.
+        while widths.iter().sum::<usize>() > available {
+            let shrinkable = |wrap_only: bool| {
+                self.columns
+                    .iter()
+                    .zip(&widths)
+                    .enumerate()
+                    .filter(|(_, (column, &w))| {
+                        column.flexible && w > column.min_width && (column.wrap || !wrap_only)
+                    })
+                    .max_by_key(|(_, (_, &w))| w)
+                    .map(|(i, _)| i)
+            };
+
+            match shrinkable(true).or_else(|| shrinkable(false)) {
+                Some(i) => widths[i] -= 1,
+                None => break,
+            }
+        }
+
+        widths
+    }
+}
+
+/// Pads `s` with spaces to `width` display columns.
+fn pad(s: &str, width: usize, align: Align) -> String {
+    let fill = " ".repeat(width.saturating_sub(UnicodeWidthStr::width(s)));
+    match align {
+        Align::Left => format!("{}{}", s, fill),
+        Align::Right => format!("{}{}", fill, s),
+    }
+}
+
+/// Shortens `s` to at most `width` display columns, ending with `…` when cut.
+/// Never splits a character.
+fn truncate(s: &str, width: usize) -> String {
+    if UnicodeWidthStr::width(s) <= width {
+        return s.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > width - 1 {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+/// Breaks `s` into lines of at most `width` display columns, splitting on
+/// whitespace and hard-breaking words that are longer than a line.
+fn wrap(s: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![String::new()];
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+
+    for word in s.split_whitespace() {
+        let mut word = word.to_string();
+        let mut word_width = UnicodeWidthStr::width(word.as_str());
+
+        if used > 0 && used + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+
+        while word_width > width {
+            let (head, tail) = split_at_width(&word, width);
+            lines.push(head);
+            word = tail;
+            word_width = UnicodeWidthStr::width(word.as_str());
+        }
+
+        if used > 0 {
+            line.push(' ');
+            used += 1;
+        }
+        line.push_str(&word);
+        used += word_width;
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn split_at_width(s: &str, width: usize) -> (String, String) {
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > width && i > 0 {
+            return (s[..i].to_string(), s[i..].to_string());
+        }
+        used += w;
+    }
+    (s.to_string(), String::new())
+}
+
+/// Wraps case-insensitive occurrences of any of `terms` in `s` with a
+/// highlight style. Padding must be applied before calling this, since the
+/// escape codes would otherwise count towards the column width.
+fn highlight_matches(s: &str, terms: &[String]) -> String {
+    if terms.is_empty() || !console::colors_enabled() {
+        return s.to_string();
+    }
+
+    let haystack = s.to_ascii_lowercase();
+    let mut matched = vec![false; s.len()];
+    for term in terms {
+        let needle = term.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        for (start, _) in haystack.match_indices(&needle) {
+            matched[start..start + needle.len()].fill(true);
+        }
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut run = String::new();
+    let mut in_match = false;
+    for (i, c) in s.char_indices() {
+        if matched[i] != in_match {
+            flush_run(&mut out, &mut run, in_match);
+            in_match = matched[i];
+        }
+        run.push(c);
+    }
+    flush_run(&mut out, &mut run, in_match);
+
+    out
+}
+
+fn flush_run(out: &mut String, run: &mut String, highlighted: bool) {
+    if highlighted {
+        out.push_str(&console::style(run.as_str()).yellow().bold().to_string());
+    } else {
+        out.push_str(run);
+    }
+    run.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn width(s: &str) -> usize {
+        UnicodeWidthStr::width(s)
+    }
+
+    #[test]
+    fn truncate_stops_at_display_width() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello world", 6), "hello…");
+        assert_eq!(truncate("anything", 0), "");
+        // Each CJK character is two columns wide, so an odd width leaves a
+        // column unused rather than splitting one.
+        assert_eq!(truncate("日本語テキスト", 6), "日本…");
+        assert_eq!(truncate("日本語テキスト", 7), "日本語…");
+        assert_eq!(truncate("🦀🦀🦀🦀", 5), "🦀🦀…");
+    }
+
+    #[test]
+    fn truncate_keeps_combining_marks_with_their_base() {
+        // "é" written as "e" + U+0301 takes one column.
+        let s = "cafe\u{301} au lait";
+        assert_eq!(truncate(s, 5), "cafe\u{301}…");
+        assert_eq!(width(&truncate(s, 5)), 5);
+    }
+
+    #[test]
+    fn wrap_fits_lines_to_width() {
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap("", 10), [""]);
+        for line in wrap("日本語のテキストを折り返す テスト 🦀🦀🦀", 5) {
+            assert!(width(&line) <= 5, "{:?} is wider than 5", line);
+        }
+    }
+
+    #[test]
+    fn split_at_width_splits_on_char_boundaries() {
+        assert_eq!(
+            split_at_width("日本語", 3),
+            ("日".to_string(), "本語".to_string())
+        );
+        assert_eq!(
+            split_at_width("🦀🦀", 2),
+            ("🦀".to_string(), "🦀".to_string())
+        );
+        assert_eq!(
+            split_at_width("e\u{301}x", 1),
+            ("e\u{301}".to_string(), "x".to_string())
+        );
+        // A character wider than the line still makes progress.
+        assert_eq!(split_at_width("日", 1), ("日".to_string(), String::new()));
+    }
+
+    #[test]
+    fn highlight_matches_non_ascii_text() {
+        console::set_colors_enabled(true);
+        let terms = vec!["nïc".to_string(), "日本".to_string()];
+        let s = "Ünïcode 日本語 🦀 cafe\u{301}";
+        let highlighted = highlight_matches(s, &terms);
+        assert_ne!(highlighted, s);
+        assert_eq!(console::strip_ansi_codes(&highlighted), s);
+    }
+}
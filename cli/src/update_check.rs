@@ -0,0 +1,122 @@
+use crate::settings;
+use crate::upgrade::{
+    get_latest_release, is_newer_version, version_to_comparable, CURRENT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+const CACHE_FILE: &str = "update-check.json";
+
+/// Set to any non-empty value other than `0`/`false` to disable the check.
+pub const DISABLE_ENV: &str = "CLAWD_NO_UPDATE_CHECK";
+
+#[derive(Serialize, Deserialize)]
+struct UpdateCache {
+    checked_at: u64,
+    /// `None` until a check succeeds
+    latest_version: Option<String>,
+}
+
+/// Starts the daily update check in the background so it overlaps with the
+/// command being run. Returns `None` when the check is disabled.
+pub fn spawn() -> Option<JoinHandle<Option<String>>> {
+    if !is_enabled() {
+        return None;
+    }
+
+    Some(tokio::spawn(latest_version()))
+}
+
+/// Waits briefly for the background check and prints a one-line notice to
+/// stderr if a newer release exists. Never fails the command.
+pub async fn notify(handle: JoinHandle<Option<String>>) {
+    let latest = match tokio::time::timeout(REQUEST_TIMEOUT, handle).await {
+        Ok(Ok(Some(latest))) => latest,
+        _ => return,
+    };
+
+    if is_newer_version(CURRENT_VERSION, &latest) {
+        eprintln!(
+            "\nA new version of clawd is available: v{} -> v{}. Run 'clawd upgrade' to update.",
+            version_to_comparable(CURRENT_VERSION),
+            latest
+        );
+    }
+}
+
+fn is_enabled() -> bool {
+    if let Ok(value) = env::var(DISABLE_ENV) {
+        if !value.is_empty() && value != "0" && value != "false" {
+            return false;
+        }
+    }
+
+    if settings::configured_update_check() == Some(false) {
+        return false;
+    }
+
+    if env::var_os("CI").is_some() {
+        return false;
+    }
+
+    std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+}
+
+async fn latest_version() -> Option<String> {
+    let path = cache_path()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    let previous = read_cache(&path);
+    if let Some(cache) = &previous {
+        if now.saturating_sub(cache.checked_at) < CHECK_INTERVAL.as_secs() {
+            return cache.latest_version.clone();
+        }
+    }
+
+    // A failed check counts as a check too, so being offline doesn't cost
+    // every command a timeout until the next day.
+    let latest = match fetch_latest_version().await {
+        Some(latest) => Some(latest),
+        None => previous.and_then(|cache| cache.latest_version),
+    };
+    let cache = UpdateCache {
+        checked_at: now,
+        latest_version: latest,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if let Ok(json) = serde_json::to_string(&cache) {
+        fs::write(&path, json).ok();
+    }
+
+    cache.latest_version
+}
+
+async fn fetch_latest_version() -> Option<String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .ok()?;
+    let release = get_latest_release(&client).await.ok()?;
+    Some(version_to_comparable(&release.tag_name))
+}
+
+fn read_cache(path: &Path) -> Option<UpdateCache> {
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn cache_path() -> Option<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "clawd")?;
+    let state_dir = dirs.state_dir().unwrap_or_else(|| dirs.cache_dir());
+    Some(state_dir.join(CACHE_FILE))
+}
@@ -1,18 +1,22 @@
+use crate::output::OutputFormat;
 use anyhow::{bail, Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::env;
-use std::fs;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 const REPO: &str = "dcodesdev/clawd";
-const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[derive(Debug, Deserialize)]
-struct Release {
-    tag_name: String,
+pub struct Release {
+    pub tag_name: String,
     assets: Vec<Asset>,
 }
 
@@ -20,6 +24,7 @@ struct Release {
 struct Asset {
     name: String,
     browser_download_url: String,
+    size: Option<u64>,
 }
 
 fn detect_platform() -> Result<String> {
@@ -50,7 +55,7 @@ fn detect_platform() -> Result<String> {
     Ok(format!("clawd-{}-{}{}", os, arch, ext))
 }
 
-async fn get_latest_release(client: &reqwest::Client) -> Result<Release> {
+pub async fn get_latest_release(client: &reqwest::Client) -> Result<Release> {
     let url = format!("https://api.github.com/repos/{}/releases/latest", REPO);
 
     let response = client
@@ -74,11 +79,11 @@ async fn get_latest_release(client: &reqwest::Client) -> Result<Release> {
         .context("Failed to parse release info")
 }
 
-fn version_to_comparable(version: &str) -> String {
+pub fn version_to_comparable(version: &str) -> String {
     version.trim_start_matches('v').to_string()
 }
 
-fn is_newer_version(current: &str, latest: &str) -> bool {
+pub fn is_newer_version(current: &str, latest: &str) -> bool {
     let current = version_to_comparable(current);
     let latest = version_to_comparable(latest);
 
@@ -99,27 +104,335 @@ fn is_newer_version(current: &str, latest: &str) -> bool {
     false
 }
 
-pub async fn execute_upgrade(force: bool) -> Result<()> {
+/// How the running binary was installed, when it's owned by a package manager
+/// that tracks the file and would be confused by it changing underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManagedInstall {
+    Homebrew,
+    Cargo,
+    Nix,
+    SystemPackage,
+}
+
+impl ManagedInstall {
+    fn detect(exe: &Path) -> Option<Self> {
+        let exe = fs::canonicalize(exe).unwrap_or_else(|_| exe.to_path_buf());
+        let path = exe.to_string_lossy();
+
+        if path.starts_with("/nix/store/") {
+            return Some(Self::Nix);
+        }
+
+        if path.contains("/Cellar/")
+            || path.starts_with("/opt/homebrew/")
+            || path.starts_with("/home/linuxbrew/.linuxbrew/")
+        {
+            return Some(Self::Homebrew);
+        }
+
+        // Compare canonical paths, since CARGO_HOME is often a symlink.
+        let cargo_bin = env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| directories::UserDirs::new().map(|d| d.home_dir().join(".cargo")))
+            .map(|home| home.join("bin"))
+            .map(|bin| fs::canonicalize(&bin).unwrap_or(bin));
+        let parent = exe
+            .parent()
+            .map(|dir| fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()));
+        if let (Some(cargo_bin), Some(parent)) = (cargo_bin, parent) {
+            if parent == cargo_bin {
+                return Some(Self::Cargo);
+            }
+        }
+
+        if path.starts_with("/usr/bin/") || path.starts_with("/usr/sbin/") {
+            return Some(Self::SystemPackage);
+        }
+
+        None
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Homebrew => "Homebrew",
+            Self::Cargo => "cargo install",
+            Self::Nix => "Nix",
+            Self::SystemPackage => "your system package manager",
+        }
+    }
+
+    fn upgrade_hint(&self) -> String {
+        match self {
+            Self::Homebrew => "brew upgrade clawd".to_string(),
+            Self::Cargo => format!(
+                "cargo install --git https://github.com/{} clawd --force",
+                REPO
+            ),
+            Self::Nix => "nix profile upgrade clawd (or update your Nix configuration)".to_string(),
+            Self::SystemPackage => {
+                "upgrade clawd with your distribution's package manager (apt, dnf, pacman, ...)"
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Runs the freshly downloaded binary with `--version` so a build that can't
+/// start on this machine never replaces the working one.
+fn smoke_test(binary: &Path) -> Result<String> {
+    let output = Command::new(binary)
+        .arg("--version")
+        .output()
+        .context("Failed to run new binary")?;
+
+    if !output.status.success() {
+        bail!(
+            "New binary exited with {} when running --version",
+            output.status
+        );
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !version.starts_with("clawd") {
+        bail!("Unexpected --version output from new binary: {:?}", version);
+    }
+
+    Ok(version)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum UpgradeStatus {
+    UpToDate,
+    Upgraded,
+    Managed,
+    RolledBack,
+}
+
+#[derive(Serialize)]
+struct UpgradeDocument {
+    status: UpgradeStatus,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    install_method: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upgrade_command: Option<String>,
+    path: PathBuf,
+}
+
+/// Where the previous binary is kept for `--rollback`.
+pub fn backup_path(current_exe: &Path) -> PathBuf {
+    current_exe.with_extension("old")
+}
+
+/// Where a new binary is downloaded before it replaces the current one.
+pub fn staging_path(current_exe: &Path) -> PathBuf {
+    current_exe.with_extension("new")
+}
+
+/// How to upgrade when the binary belongs to a package manager, e.g.
+/// "installed with Homebrew; run: brew upgrade clawd".
+pub fn managed_install_hint(current_exe: &Path) -> Option<String> {
+    ManagedInstall::detect(current_exe).map(|managed| {
+        format!(
+            "installed with {}; run: {}",
+            managed.name(),
+            managed.upgrade_hint()
+        )
+    })
+}
+
+pub fn execute_rollback(output: OutputFormat) -> Result<()> {
+    let current_exe = env::current_exe().context("Failed to get current executable path")?;
+    let backup_path = backup_path(&current_exe);
+
+    if !backup_path.exists() {
+        bail!(
+            "No previous version found at {} to roll back to",
+            backup_path.display()
+        );
+    }
+
+    let version = smoke_test(&backup_path).context("Previous binary failed the smoke test")?;
+
+    #[cfg(unix)]
+    {
+        if let Err(e) = fs::rename(&backup_path, &current_exe) {
+            bail!("Failed to restore previous binary (try with sudo): {}", e);
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let batch_content = format!(
+            r#"@echo off
+timeout /t 1 /nobreak >nul
+move /y "{}" "{}"
+del "%~f0"
+"#,
+            backup_path.display(),
+            current_exe.display()
+        );
+        let batch_path = current_exe.with_extension("bat");
+        fs::write(&batch_path, batch_content)?;
+        Command::new("cmd")
+            .args(["/C", "start", "/min", "", batch_path.to_str().unwrap()])
+            .spawn()?;
+    }
+
+    output.status(format_args!("Rolled back to {}", version));
+    output.print(&UpgradeDocument {
+        status: UpgradeStatus::RolledBack,
+        version: version_to_comparable(version.trim_start_matches("clawd").trim()),
+        latest_version: None,
+        previous_version: Some(version_to_comparable(CURRENT_VERSION)),
+        install_method: None,
+        upgrade_command: None,
+        path: current_exe,
+    })?;
+
+    Ok(())
+}
+
+/// Streams the release asset to `path` chunk by chunk, showing byte progress,
+/// and checks that the number of bytes written matches the expected size.
+async fn download_to_file(
+    client: &reqwest::Client,
+    asset: &Asset,
+    path: &Path,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut response = client
+        .get(&asset.browser_download_url)
+        .header("User-Agent", "clawd-cli")
+        .send()
+        .await
+        .context("Failed to download binary")?;
+
+    if !response.status().is_success() {
+        bail!("Failed to download: HTTP {}", response.status().as_u16());
+    }
+
+    let expected_size = response.content_length().or(asset.size);
+
+    let pb = match expected_size {
+        _ if output.is_structured() => ProgressBar::hidden(),
+        Some(size) => {
+            let pb = ProgressBar::new(size);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template(
+                        "{spinner:.green} Downloading [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec})",
+                    )
+                    .unwrap()
+                    .progress_chars("=> "),
+            );
+            pb
+        }
+        None => {
+            let pb = ProgressBar::new_spinner();
+            pb.set_style(
+                ProgressStyle::default_spinner()
+                    .template("{spinner:.green} Downloading {bytes} ({bytes_per_sec})")
+                    .unwrap(),
+            );
+            pb
+        }
+    };
+
+    let mut file = File::create(path).context("Failed to create new binary")?;
+    let mut written: u64 = 0;
+
+    while let Some(chunk) = response.chunk().await.context("Failed to read binary")? {
+        file.write_all(&chunk)
+            .context("Failed to write new binary")?;
+        written += chunk.len() as u64;
+        pb.set_position(written);
+    }
+
+    file.flush().context("Failed to write new binary")?;
+    pb.finish_and_clear();
+
+    if let Some(size) = expected_size {
+        if written != size {
+            bail!(
+                "Downloaded binary is incomplete: expected {} bytes, got {}",
+                size,
+                written
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn execute_upgrade(force: bool, allow_managed: bool, output: OutputFormat) -> Result<()> {
+    let current_exe = env::current_exe().context("Failed to get current executable path")?;
+    let current_version = version_to_comparable(CURRENT_VERSION);
+
+    if let Some(managed) = ManagedInstall::detect(&current_exe) {
+        if !force && !allow_managed {
+            output.status(format_args!(
+                "clawd at {} was installed with {}.",
+                current_exe.display(),
+                managed.name()
+            ));
+            output.status(format_args!("To upgrade, run: {}", managed.upgrade_hint()));
+            output.status("Or pass --force or --allow-managed to replace the binary anyway.");
+            output.print(&UpgradeDocument {
+                status: UpgradeStatus::Managed,
+                version: current_version,
+                latest_version: None,
+                previous_version: None,
+                install_method: Some(managed.name()),
+                upgrade_command: Some(managed.upgrade_hint()),
+                path: current_exe,
+            })?;
+            return Ok(());
+        }
+        output.status(format_args!(
+            "Warning: replacing a binary managed by {} ({})",
+            managed.name(),
+            if allow_managed {
+                "--allow-managed"
+            } else {
+                "--force"
+            }
+        ));
+    }
+
     let client = reqwest::Client::new();
 
-    println!("Checking for updates...");
+    output.status("Checking for updates...");
 
     let release = get_latest_release(&client).await?;
     let latest_version = version_to_comparable(&release.tag_name);
-    let current_version = version_to_comparable(CURRENT_VERSION);
 
-    println!("Current version: v{}", current_version);
-    println!("Latest version:  v{}", latest_version);
+    output.status(format_args!("Current version: v{}", current_version));
+    output.status(format_args!("Latest version:  v{}", latest_version));
 
     if !force && !is_newer_version(&current_version, &latest_version) {
-        println!("\nYou're already on the latest version!");
+        output.status("\nYou're already on the latest version!");
+        output.print(&UpgradeDocument {
+            status: UpgradeStatus::UpToDate,
+            version: current_version,
+            latest_version: Some(latest_version),
+            previous_version: None,
+            install_method: None,
+            upgrade_command: None,
+            path: current_exe,
+        })?;
         return Ok(());
     }
 
     if force && current_version == latest_version {
-        println!("\nForce reinstalling v{}...", latest_version);
+        output.status(format_args!("\nForce reinstalling v{}...", latest_version));
     } else {
-        println!("\nUpgrading to v{}...", latest_version);
+        output.status(format_args!("\nUpgrading to v{}...", latest_version));
     }
 
     let binary_name = detect_platform()?;
@@ -130,37 +443,25 @@ pub async fn execute_upgrade(force: bool) -> Result<()> {
         .find(|a| a.name == binary_name)
         .context(format!("No binary found for platform: {}", binary_name))?;
 
-    // Download the new binary
-    let pb = ProgressBar::new_spinner();
+    // Download the new binary into a temp file in the same directory
+    let temp_path = staging_path(&current_exe);
+    if let Err(e) = download_to_file(&client, asset, &temp_path, output).await {
+        fs::remove_file(&temp_path).ok();
+        return Err(e);
+    }
+
+    let pb = if output.is_structured() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new_spinner()
+    };
     pb.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.green} {msg}")
             .unwrap(),
     );
-    pb.set_message("Downloading...");
-
-    let response = client
-        .get(&asset.browser_download_url)
-        .header("User-Agent", "clawd-cli")
-        .send()
-        .await
-        .context("Failed to download binary")?;
-
-    if !response.status().is_success() {
-        bail!("Failed to download: HTTP {}", response.status().as_u16());
-    }
-
-    let bytes = response.bytes().await.context("Failed to read binary")?;
-
     pb.set_message("Installing...");
 
-    // Get current executable path
-    let current_exe = env::current_exe().context("Failed to get current executable path")?;
-
-    // Create temp file in the same directory
-    let temp_path = current_exe.with_extension("new");
-    fs::write(&temp_path, &bytes).context("Failed to write new binary")?;
-
     // Set executable permissions on Unix
     #[cfg(unix)]
     {
@@ -169,10 +470,17 @@ pub async fn execute_upgrade(force: bool) -> Result<()> {
         fs::set_permissions(&temp_path, perms)?;
     }
 
+    pb.set_message("Verifying...");
+
+    if let Err(e) = smoke_test(&temp_path) {
+        fs::remove_file(&temp_path).ok();
+        return Err(e.context("New binary failed the smoke test, keeping current version"));
+    }
+
     // Replace old binary with new one
     #[cfg(unix)]
     {
-        let backup_path = current_exe.with_extension("old");
+        let backup_path = backup_path(&current_exe);
         // Move current to backup
         if let Err(e) = fs::rename(&current_exe, &backup_path) {
             // Try with elevated permissions hint
@@ -185,13 +493,14 @@ pub async fn execute_upgrade(force: bool) -> Result<()> {
             fs::rename(&backup_path, &current_exe).ok();
             bail!("Failed to install new binary: {}", e);
         }
-        // Remove backup
-        fs::remove_file(&backup_path).ok();
+        // Keep the backup around for `clawd upgrade --rollback`
     }
 
     #[cfg(windows)]
     {
         // On Windows, we need to use a different approach since the running exe is locked
+        fs::copy(&current_exe, backup_path(&current_exe))
+            .context("Failed to back up current binary")?;
         let batch_content = format!(
             r#"@echo off
 timeout /t 1 /nobreak >nul
@@ -203,15 +512,27 @@ del "%~f0"
         );
         let batch_path = current_exe.with_extension("bat");
         fs::write(&batch_path, batch_content)?;
-        std::process::Command::new("cmd")
+        Command::new("cmd")
             .args(["/C", "start", "/min", "", batch_path.to_str().unwrap()])
             .spawn()?;
     }
 
     pb.finish_with_message("Done!");
 
-    println!("\nSuccessfully upgraded to v{}!", latest_version);
-    println!("Run 'clawd --version' to verify.");
+    output.status(format_args!(
+        "\nSuccessfully upgraded to v{}!",
+        latest_version
+    ));
+    output.status("Run 'clawd upgrade --rollback' to restore the previous version.");
+    output.print(&UpgradeDocument {
+        status: UpgradeStatus::Upgraded,
+        version: latest_version,
+        latest_version: None,
+        previous_version: Some(current_version),
+        install_method: None,
+        upgrade_command: None,
+        path: current_exe,
+    })?;
 
     Ok(())
 }
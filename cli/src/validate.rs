@@ -0,0 +1,498 @@
+use crate::category::Category;
+use crate::error::ClawdError;
+use crate::output::OutputFormat;
+use anyhow::{Context, Result};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const README: &str = "README.mdx";
+const SKILL_MD: &str = "SKILL.md";
+
+// Limits Claude enforces when loading a skill
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// A single validation failure, pointing at the offending line when known.
+#[derive(Serialize)]
+pub struct Problem {
+    pub file: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file.display(), line, self.message),
+            None => write!(f, "{}: {}", self.file.display(), self.message),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ValidationReport {
+    skills: usize,
+    valid: bool,
+    problems: Vec<Problem>,
+}
+
+/// Schemas and author list loaded from the root of a registry checkout.
+struct Registry {
+    skills_dir: PathBuf,
+    readme_schema: Validator,
+    skill_schema: Validator,
+    authors: Map<String, Value>,
+}
+
+impl Registry {
+    fn load(root: &Path) -> Result<Self> {
+        Self::load_with_authors(root.join("skills"), &root.join("authors.json"))
+    }
+
+    /// Loads the schemas next to `skills_dir` and the authors in `authors`.
+    fn load_with_authors(skills_dir: PathBuf, authors: &Path) -> Result<Self> {
+        let root = skills_dir.parent().unwrap_or(&skills_dir);
+
+        // Categories are checked against `Category` instead, the list
+        // `clawd new` and `clawd publish` offer.
+        let readme_schema_path = root.join("readme-schema.json");
+        let mut readme_schema: Value = read_json(&readme_schema_path)?;
+        if let Some(Value::Object(category)) = readme_schema.pointer_mut("/properties/category") {
+            category.remove("enum");
+        }
+
+        Ok(Self {
+            readme_schema: compile_schema(&readme_schema, &readme_schema_path)?,
+            skill_schema: load_schema(&root.join("skill-schema.json"))?,
+            authors: read_json(authors)?,
+            skills_dir,
+        })
+    }
+
+    /// Every `skills/<author>/<name>` directory, sorted.
+    fn skill_dirs(&self) -> Result<Vec<PathBuf>> {
+        let mut dirs = Vec::new();
+        for author in sorted_subdirs(&self.skills_dir)? {
+            dirs.extend(sorted_subdirs(&author)?);
+        }
+        Ok(dirs)
+    }
+
+    fn check_skill(&self, dir: &Path, problems: &mut Vec<Problem>) {
+        let readme_path = dir.join(README);
+        let Some(readme) = read_frontmatter(&readme_path, problems) else {
+            return;
+        };
+        check_schema(&self.readme_schema, &readme, &readme_path, problems);
+
+        let mut problem = |path: &[&str], message: String| {
+            problems.push(Problem {
+                file: readme_path.clone(),
+                line: Some(readme.line_of(path)),
+                message,
+            });
+        };
+
+        let expected_id = dir
+            .strip_prefix(&self.skills_dir)
+            .unwrap_or(dir)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if let Some(id) = readme.data.get("id").and_then(Value::as_str) {
+            if id != expected_id {
+                problem(
+                    &["id"],
+                    format!(
+                        "id '{}' does not match the directory layout, expected '{}'",
+                        id, expected_id
+                    ),
+                );
+            }
+        }
+
+        if let Some(author) = readme.data.get("author").and_then(Value::as_str) {
+            if !self.authors.contains_key(author) {
+                problem(
+                    &["author"],
+                    format!("author '{}' is not listed in authors.json", author),
+                );
+            }
+        }
+
+        if let Some(category) = readme.data.get("category").and_then(Value::as_str) {
+            if Category::from_str(category).is_none() {
+                let allowed: Vec<&str> = Category::ALL.iter().map(Category::as_str).collect();
+                problem(
+                    &["category"],
+                    format!(
+                        "category '{}' is not one of {}",
+                        category,
+                        allowed.join(", ")
+                    ),
+                );
+            }
+        }
+
+        if let Some(contributors) = readme.data.get("contributors").and_then(Value::as_array) {
+            for (i, contributor) in contributors.iter().enumerate() {
+                let Some(contributor) = contributor.as_str() else {
+                    continue;
+                };
+                if !self.authors.contains_key(contributor) {
+                    problem(
+                        &["contributors", &i.to_string()],
+                        format!(
+                            "contributor '{}' is not listed in authors.json",
+                            contributor
+                        ),
+                    );
+                }
+            }
+        }
+
+        // SKILL.md usually lives in the upstream repo; validate it when the
+        // entry vendors a copy.
+        let skill_md_path = dir.join("skill").join(SKILL_MD);
+        if skill_md_path.is_file() {
+            if let Some(skill_md) = read_frontmatter(&skill_md_path, problems) {
+                check_schema(&self.skill_schema, &skill_md, &skill_md_path, problems);
+                check_skill_limits(&skill_md, &skill_md_path, problems);
+            }
+        }
+    }
+}
+
+/// Parsed YAML frontmatter, keeping the raw lines so problems can be mapped
+/// back to a line in the file.
+pub struct Frontmatter {
+    pub data: Value,
+    lines: Vec<String>,
+}
+
+impl Frontmatter {
+    /// Line of the opening `---`.
+    const START_LINE: usize = 1;
+
+    /// Parses the `---` delimited block at the top of `contents`. Errors
+    /// carry the file line they refer to.
+    pub fn parse(contents: &str) -> Result<Self, (usize, String)> {
+        let mut lines = contents.lines();
+        if lines.next().map(str::trim_end) != Some("---") {
+            return Err((Self::START_LINE, "missing frontmatter".to_string()));
+        }
+
+        let mut yaml = Vec::new();
+        loop {
+            match lines.next() {
+                Some(line) if line.trim_end() == "---" => break,
+                Some(line) => yaml.push(line.to_string()),
+                None => return Err((Self::START_LINE, "unterminated frontmatter".to_string())),
+            }
+        }
+
+        let data = serde_yaml::from_str(&yaml.join("\n")).map_err(|e| {
+            let mut message = e.to_string();
+            let mut line = Self::START_LINE;
+            // Report the position as a file line rather than one relative to
+            // the frontmatter.
+            if let Some(location) = e.location() {
+                message = message.replace(
+                    &format!(" at line {} column {}", location.line(), location.column()),
+                    "",
+                );
+                line += location.line();
+            }
+            (line, format!("invalid YAML: {}", message))
+        })?;
+
+        Ok(Self { data, lines: yaml })
+    }
+
+    /// Best-effort line number for a path into the frontmatter: the line of
+    /// the top-level key, or of the nth `- ` item below it for list indexes.
+    pub fn line_of(&self, path: &[&str]) -> usize {
+        let Some(key) = path.first() else {
+            return Self::START_LINE;
+        };
+        let Some(key_index) = self.lines.iter().position(|line| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        }) else {
+            return Self::START_LINE;
+        };
+        let key_line = key_index + Self::START_LINE + 1;
+
+        let Some(Ok(item)) = path.get(1).map(|s| s.parse::<usize>()) else {
+            return key_line;
+        };
+        self.lines[key_index + 1..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, line)| line.is_empty() || line.starts_with([' ', '-']))
+            .filter(|(_, line)| line.trim_start().starts_with('-'))
+            .nth(item)
+            .map_or(key_line, |(offset, _)| key_line + offset + 1)
+    }
+}
+
+pub fn execute_validate(path: Option<PathBuf>, output: OutputFormat) -> Result<()> {
+    let target = path.unwrap_or_else(|| PathBuf::from("."));
+    let target = target
+        .canonicalize()
+        .with_context(|| format!("Cannot access {}", target.display()))?;
+
+    let root = find_registry_root(&target)?;
+    let (checked, problems) = check_path(&root, &target)?;
+
+    let count = problems.len();
+    if output.is_structured() {
+        output.print(&ValidationReport {
+            skills: checked,
+            valid: problems.is_empty(),
+            problems,
+        })?;
+        if count > 0 {
+            return Err(ClawdError::ValidationFailed(count).into());
+        }
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    if count > 0 {
+        println!();
+        return Err(ClawdError::ValidationFailed(count).into());
+    }
+
+    println!("✅ {} skills validated, no problems found", checked);
+    Ok(())
+}
+
+/// Validates every skill in the registry at `root` that lives under (or
+/// contains) `target`. Returns how many skills were checked and the problems
+/// found, with paths relative to the current directory where possible.
+pub fn check_path(root: &Path, target: &Path) -> Result<(usize, Vec<Problem>)> {
+    let registry = Registry::load(root)?;
+    let skill_dirs: Vec<PathBuf> = registry
+        .skill_dirs()?
+        .into_iter()
+        .filter(|dir| dir.starts_with(target) || target.starts_with(dir))
+        .collect();
+    Ok((skill_dirs.len(), check_dirs(&registry, &skill_dirs)))
+}
+
+/// Validates every skill in `skills_dir` as `clawd validate` does, against
+/// the schemas in its parent directory and the authors in `authors`.
+pub fn check_skills_dir(skills_dir: &Path, authors: &Path) -> Result<Vec<Problem>> {
+    let registry = Registry::load_with_authors(skills_dir.to_path_buf(), authors)?;
+    Ok(check_dirs(&registry, &registry.skill_dirs()?))
+}
+
+/// The problems in `skill_dirs`, sorted, with paths relative to the current
+/// directory where possible.
+fn check_dirs(registry: &Registry, skill_dirs: &[PathBuf]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for dir in skill_dirs {
+        registry.check_skill(dir, &mut problems);
+    }
+
+    problems.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    let cwd = std::env::current_dir().unwrap_or_default();
+    for problem in &mut problems {
+        if let Ok(relative) = problem.file.strip_prefix(&cwd) {
+            problem.file = relative.to_path_buf();
+        }
+    }
+    problems
+}
+
+/// Finds the registry checkout containing `path`: the nearest directory with
+/// an `authors.json` and a `skills/` directory.
+pub fn find_registry_root(path: &Path) -> Result<PathBuf, ClawdError> {
+    path.ancestors()
+        .find(|dir| dir.join("authors.json").is_file() && dir.join("skills").is_dir())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| ClawdError::NoRegistryRoot(path.display().to_string()))
+}
+
+fn read_frontmatter(path: &Path, problems: &mut Vec<Problem>) -> Option<Frontmatter> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            problems.push(Problem {
+                file: path.to_path_buf(),
+                line: None,
+                message: format!("cannot read file: {}", e),
+            });
+            return None;
+        }
+    };
+    match Frontmatter::parse(&contents) {
+        Ok(frontmatter) => Some(frontmatter),
+        Err((line, message)) => {
+            problems.push(Problem {
+                file: path.to_path_buf(),
+                line: Some(line),
+                message,
+            });
+            None
+        }
+    }
+}
+
+/// Checks the SKILL.md of a freshly downloaded skill in `dir`, which will be
+/// installed as `dir_name`. Problems that would stop Claude from loading the
+/// skill are returned as an error; the `Ok` value holds warnings.
+pub fn check_installed_skill(dir: &Path, dir_name: &str) -> Result<Vec<String>, ClawdError> {
+    let path = dir.join(SKILL_MD);
+    if !path.is_file() {
+        return Err(ClawdError::InvalidSkill(format!("no {} found", SKILL_MD)));
+    }
+
+    let mut problems = Vec::new();
+    let Some(frontmatter) = read_frontmatter(&path, &mut problems) else {
+        return Err(invalid_skill(problems));
+    };
+
+    let schema = jsonschema::validator_for(&installed_skill_schema())
+        .map_err(|e| ClawdError::InvalidResponse(e.to_string()))?;
+
+    check_schema(&schema, &frontmatter, &path, &mut problems);
+    check_skill_limits(&frontmatter, &path, &mut problems);
+    if !problems.is_empty() {
+        return Err(invalid_skill(problems));
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(name) = frontmatter.data.get("name").and_then(Value::as_str) {
+        if name != dir_name {
+            warnings.push(format!(
+                "{} name '{}' differs from the install directory '{}'",
+                SKILL_MD, name, dir_name
+            ));
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// The registry's `skill-schema.json`, built into the binary. Unlike the
+/// registry, installs allow extra fields such as `license` or `allowed-tools`.
+fn installed_skill_schema() -> Value {
+    let mut schema: Value = serde_json::from_str(include_str!("../../skill-schema.json"))
+        .expect("skill-schema.json is valid JSON");
+    schema["additionalProperties"] = Value::Bool(true);
+    schema
+}
+
+fn invalid_skill(problems: Vec<Problem>) -> ClawdError {
+    let problems: Vec<String> = problems
+        .into_iter()
+        .map(|problem| match problem.line {
+            Some(line) => format!("{}:{}: {}", SKILL_MD, line, problem.message),
+            None => format!("{}: {}", SKILL_MD, problem.message),
+        })
+        .collect();
+    ClawdError::InvalidSkill(problems.join("; "))
+}
+
+/// Length limits Claude applies to SKILL.md `name` and `description`.
+fn check_skill_limits(frontmatter: &Frontmatter, file: &Path, problems: &mut Vec<Problem>) {
+    for (key, max) in [("name", MAX_NAME_LEN), ("description", MAX_DESCRIPTION_LEN)] {
+        let Some(value) = frontmatter.data.get(key).and_then(Value::as_str) else {
+            continue;
+        };
+        let len = value.chars().count();
+        if len > max {
+            problems.push(Problem {
+                file: file.to_path_buf(),
+                line: Some(frontmatter.line_of(&[key])),
+                message: format!("{} is {} characters long, the limit is {}", key, len, max),
+            });
+        }
+    }
+}
+
+fn check_schema(
+    schema: &Validator,
+    frontmatter: &Frontmatter,
+    file: &Path,
+    problems: &mut Vec<Problem>,
+) {
+    for error in schema.iter_errors(&frontmatter.data) {
+        let mut path: Vec<String> = error
+            .instance_path()
+            .iter()
+            .map(|segment| segment.to_string())
+            .collect();
+        if let ValidationErrorKind::AdditionalProperties { unexpected } = error.kind() {
+            path.extend(unexpected.first().cloned());
+        }
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+
+        let message = if path.is_empty() {
+            error.to_string()
+        } else {
+            format!("{}: {}", path.join("."), error)
+        };
+
+        problems.push(Problem {
+            file: file.to_path_buf(),
+            line: Some(frontmatter.line_of(&path)),
+            message,
+        });
+    }
+}
+
+fn load_schema(path: &Path) -> Result<Validator> {
+    compile_schema(&read_json(path)?, path)
+}
+
+fn compile_schema(schema: &Value, path: &Path) -> Result<Validator> {
+    jsonschema::validator_for(schema)
+        .map_err(|e| anyhow::anyhow!("Invalid schema {}: {}", path.display(), e))
+}
+
+pub fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+pub fn sorted_subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Cannot read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn installed_skill_schema_allows_extra_fields() {
+        let schema = jsonschema::validator_for(&installed_skill_schema()).unwrap();
+        assert!(schema.is_valid(&json!({
+            "name": "my-skill",
+            "description": "Does things",
+            "license": "MIT",
+        })));
+        assert!(!schema.is_valid(&json!({ "name": "My Skill", "description": "x" })));
+        assert!(!schema.is_valid(&json!({ "name": "my-skill" })));
+    }
+}